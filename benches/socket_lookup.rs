@@ -0,0 +1,153 @@
+// Compare socket lookup by linear scan of `/proc/net/tcp` entries with `SocketIndex`.
+// `src/socket.rs` is included as is, and the modules it depends on are replaced by stubs
+// because procs has no library target.
+//
+// make bench
+
+#[cfg(target_os = "linux")]
+#[allow(dead_code)]
+#[path = "../src/socket.rs"]
+mod socket;
+
+// Stubs of `crate::process` and `crate::util` used by `socket.rs`
+#[cfg(target_os = "linux")]
+mod process {
+    use crate::socket::SocketTable;
+
+    pub struct ProcessTask;
+
+    impl ProcessTask {
+        pub fn is_record(&self) -> bool {
+            false
+        }
+
+        pub fn socket_table(&self) -> Option<&SocketTable> {
+            None
+        }
+    }
+
+    pub struct ProcessInfo {
+        pub pid: i32,
+        pub curr_proc: ProcessTask,
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod util {
+    use std::path::{Path, PathBuf};
+
+    pub fn procfs_file(procfs: &Option<PathBuf>, name: &str) -> PathBuf {
+        procfs.as_deref().unwrap_or(Path::new("/proc")).join(name)
+    }
+
+    pub fn procfs_system_info(_procfs: &Option<PathBuf>) -> procfs::ExplicitSystemInfo {
+        procfs::ExplicitSystemInfo {
+            boot_time_secs: 0,
+            ticks_per_second: 100,
+            page_size: 4096,
+            is_little_endian: cfg!(target_endian = "little"),
+        }
+    }
+
+    // All processes are in the default network namespace
+    pub fn ns_inode(_pid: i32, _procfs: &Option<PathBuf>, _ns: &str) -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use crate::process::{ProcessInfo, ProcessTask};
+    use crate::socket::{Socket, SocketIndex};
+    use criterion::{BenchmarkId, Criterion};
+    use procfs::FromReadSI;
+    use procfs::net::TcpNetEntries;
+    use std::fmt::Write;
+    use std::hint::black_box;
+    use std::path::PathBuf;
+
+    const PROCESSES: u64 = 1_000;
+
+    fn gen_tcp(n: u64) -> String {
+        let mut text = String::from(
+            "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n",
+        );
+        for i in 0..n {
+            let _ = writeln!(
+                text,
+                "{:4}: 0100007F:{:04X} 0100007F:{:04X} 01 00000000:00000000 00:00000000 00000000  1000        0 {} 1 0000000000000000 20 4 30 10 -1",
+                i,
+                i % 0x10000,
+                (i + 1) % 0x10000,
+                i + 1
+            );
+        }
+        text
+    }
+
+    // Synthetic procfs which has `n` TCP sockets
+    fn gen_procfs(n: u64) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("procs_bench_{}_{n}", std::process::id()));
+        std::fs::create_dir_all(root.join("net")).unwrap();
+        std::fs::write(root.join("net/tcp"), gen_tcp(n)).unwrap();
+        root
+    }
+
+    pub fn bench_socket_lookup(c: &mut Criterion) {
+        let mut group = c.benchmark_group("socket_lookup");
+        group.sample_size(10);
+        for n in [1_000, 10_000, 100_000] {
+            // Each process has sockets spread over the whole table
+            let socks: Vec<(i32, u64)> = (0..PROCESSES)
+                .map(|pid| (pid as i32, pid * (n / PROCESSES) + 1))
+                .collect();
+            let procfs = gen_procfs(n);
+
+            group.bench_with_input(BenchmarkId::new("linear", n), &n, |b, _| {
+                let si = crate::util::procfs_system_info(&None);
+                let text = std::fs::read_to_string(procfs.join("net/tcp")).unwrap();
+                let tcp = TcpNetEntries::from_read(text.as_bytes(), &si).unwrap().0;
+                let tcp6 = Vec::new();
+                b.iter(|| {
+                    socks
+                        .iter()
+                        .filter(|(_, sock)| {
+                            tcp.iter()
+                                .chain(tcp6.iter())
+                                .find(|x| x.inode == black_box(*sock))
+                                .is_some()
+                        })
+                        .count()
+                })
+            });
+
+            group.bench_with_input(BenchmarkId::new("indexed", n), &n, |b, _| {
+                let mut index = SocketIndex::new(&Some(procfs.clone()));
+                b.iter(|| {
+                    socks
+                        .iter()
+                        .filter(|(pid, sock)| {
+                            let proc = ProcessInfo {
+                                pid: *pid,
+                                curr_proc: ProcessTask,
+                            };
+                            let table = index.table(&proc).unwrap();
+                            matches!(table.get(black_box(*sock)), Some(Socket::Tcp(_)))
+                        })
+                        .count()
+                })
+            });
+
+            let _ = std::fs::remove_dir_all(&procfs);
+        }
+        group.finish();
+    }
+}
+
+#[cfg(target_os = "linux")]
+criterion::criterion_group!(benches, linux::bench_socket_lookup);
+#[cfg(target_os = "linux")]
+criterion::criterion_main!(benches);
+
+#[cfg(not(target_os = "linux"))]
+fn main() {}
@@ -0,0 +1,191 @@
+use crate::config::ConfigColumnMode;
+use procfs::process::Status;
+use std::path::PathBuf;
+
+// Indexed by capability number. See include/uapi/linux/capability.h
+const NAMES: [&str; 41] = [
+    "cap_chown",
+    "cap_dac_override",
+    "cap_dac_read_search",
+    "cap_fowner",
+    "cap_fsetid",
+    "cap_kill",
+    "cap_setgid",
+    "cap_setuid",
+    "cap_setpcap",
+    "cap_linux_immutable",
+    "cap_net_bind_service",
+    "cap_net_broadcast",
+    "cap_net_admin",
+    "cap_net_raw",
+    "cap_ipc_lock",
+    "cap_ipc_owner",
+    "cap_sys_module",
+    "cap_sys_rawio",
+    "cap_sys_chroot",
+    "cap_sys_ptrace",
+    "cap_sys_pacct",
+    "cap_sys_admin",
+    "cap_sys_boot",
+    "cap_sys_nice",
+    "cap_sys_resource",
+    "cap_sys_time",
+    "cap_sys_tty_config",
+    "cap_mknod",
+    "cap_lease",
+    "cap_audit_write",
+    "cap_audit_control",
+    "cap_setfcap",
+    "cap_mac_override",
+    "cap_mac_admin",
+    "cap_syslog",
+    "cap_wake_alarm",
+    "cap_block_suspend",
+    "cap_audit_read",
+    "cap_perfmon",
+    "cap_bpf",
+    "cap_checkpoint_restore",
+];
+
+/// Names of capabilities in `mask` like `cap_net_bind_service`.
+/// Capabilities unknown to procs are shown by number like `cap_41`.
+pub fn names(mask: u64) -> Vec<String> {
+    (0..64)
+        .filter(|x| mask & (1 << x) != 0)
+        .map(|x| {
+            NAMES
+                .get(x)
+                .map(|x| x.to_string())
+                .unwrap_or_else(|| format!("cap_{x}"))
+        })
+        .collect()
+}
+
+/// The last capability supported by the kernel
+pub fn last_cap(procfs: &Option<PathBuf>) -> u32 {
+    let path = crate::util::procfs_file(procfs, "sys/kernel/cap_last_cap");
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|x| x.trim().parse().ok())
+        .unwrap_or(NAMES.len() as u32 - 1)
+}
+
+/// Capability sets in `/proc/<pid>/status`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CapSet {
+    Amb,
+    Bnd,
+    Eff,
+    Prm,
+}
+
+impl CapSet {
+    pub fn header(&self) -> &'static str {
+        match self {
+            CapSet::Amb => "CapAmb",
+            CapSet::Bnd => "CapBnd",
+            CapSet::Eff => "CapEff",
+            CapSet::Prm => "CapPrm",
+        }
+    }
+
+    /// `CapAmb` and `CapBnd` are missing on old kernels
+    pub fn mask(&self, status: &Status) -> Option<u64> {
+        match self {
+            CapSet::Amb => status.capamb,
+            CapSet::Bnd => status.capbnd,
+            CapSet::Eff => Some(status.capeff),
+            CapSet::Prm => Some(status.capprm),
+        }
+    }
+}
+
+/// Format `mask` by `Name`, `Compact` or `Hex` mode.
+/// `Compact` shows `full` if all capabilities up to `last_cap` are set, and `none` if no capability is set.
+/// Otherwise it shows the libcap style text of `set`.
+pub fn format(mask: u64, last_cap: u32, set: CapSet, mode: &ConfigColumnMode) -> String {
+    let full = u64::MAX >> (63 - last_cap.min(63));
+    match mode {
+        ConfigColumnMode::Hex => format!("{mask:016x}"),
+        ConfigColumnMode::Compact if mask & full == full => String::from("full"),
+        ConfigColumnMode::Compact if mask == 0 => String::from("none"),
+        ConfigColumnMode::Compact => compact(mask, full, set),
+        _ => names(mask).join(","),
+    }
+}
+
+// Effective and permitted sets are shown like `cap_chown,cap_kill=e` or `=e cap_sys_admin-e`
+// whichever is shorter as `cap_to_text(3)`.
+// Bounding and ambient sets are shown like `!cap_sys_admin` or `^cap_net_raw` as `cap_iab_to_text(3)`.
+fn compact(mask: u64, full: u64, set: CapSet) -> String {
+    let raised = names(mask);
+    let dropped = names(!mask & full);
+    let prefixed = |names: Vec<String>, prefix: &str| {
+        names
+            .iter()
+            .map(|x| format!("{prefix}{x}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let flag = match set {
+        CapSet::Amb => return prefixed(raised, "^"),
+        CapSet::Bnd => return prefixed(dropped, "!"),
+        CapSet::Eff => "e",
+        CapSet::Prm => "p",
+    };
+    if dropped.len() < raised.len() {
+        format!("={flag} {}-{flag}", dropped.join(","))
+    } else {
+        format!("{}={flag}", raised.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(names(0), Vec::<String>::new());
+        assert_eq!(
+            names((1 << 10) | (1 << 21)),
+            vec!["cap_net_bind_service", "cap_sys_admin"]
+        );
+        assert_eq!(names(1 << 41), vec!["cap_41"]);
+    }
+
+    #[test]
+    fn test_format() {
+        let full = 0x1ff_ffff_ffff;
+        let eff = CapSet::Eff;
+        assert_eq!(format(full, 40, eff, &ConfigColumnMode::Compact), "full");
+        assert_eq!(format(0, 40, eff, &ConfigColumnMode::Compact), "none");
+        assert_eq!(format(1, 40, eff, &ConfigColumnMode::Name), "cap_chown");
+        assert_eq!(format(0, 40, eff, &ConfigColumnMode::Name), "");
+        assert_eq!(
+            format(full, 40, eff, &ConfigColumnMode::Hex),
+            "000001ffffffffff"
+        );
+    }
+
+    #[test]
+    fn test_format_compact() {
+        let full = 0x1ff_ffff_ffff;
+        let mode = ConfigColumnMode::Compact;
+        assert_eq!(format(1, 40, CapSet::Eff, &mode), "cap_chown=e");
+        assert_eq!(format(0x21, 40, CapSet::Prm, &mode), "cap_chown,cap_kill=p");
+        assert_eq!(
+            format(full & !(1 << 21), 40, CapSet::Eff, &mode),
+            "=e cap_sys_admin-e"
+        );
+        assert_eq!(
+            format(full & !(1 << 21), 40, CapSet::Bnd, &mode),
+            "!cap_sys_admin"
+        );
+        assert_eq!(format(1 << 13, 40, CapSet::Amb, &mode), "^cap_net_raw");
+
+        // Capabilities beyond `last_cap` are not required for `full`
+        assert_eq!(format(0xff, 7, CapSet::Eff, &mode), "full");
+        assert_eq!(format(0x7f, 7, CapSet::Eff, &mode), "=e cap_setuid-e");
+    }
+}
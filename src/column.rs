@@ -1,5 +1,101 @@
-use crate::config::{Config, ConfigColumnAlign, ConfigSortOrder};
-use crate::process::ProcessInfo;
+use crate::config::{Config, ConfigColumnAlign, ConfigColumnMode, ConfigSortOrder};
+use crate::process::{DataSource, ProcessInfo};
+use anyhow::{Error, bail};
+use chrono::{DateTime, Duration, Local};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RawValue {
+    I64(i64),
+    U64(u64),
+    /// Ratio, percent or seconds with fraction
+    Number(f64),
+    Text(String),
+}
+
+impl RawValue {
+    /// Numeric value for comparison, or `None` if the value is text
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            RawValue::I64(x) => Some(*x as f64),
+            RawValue::U64(x) => Some(*x as f64),
+            RawValue::Number(x) => Some(*x),
+            RawValue::Text(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for RawValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RawValue::I64(x) => write!(f, "{x}"),
+            RawValue::U64(x) => write!(f, "{x}"),
+            RawValue::Number(x) => write!(f, "{x}"),
+            RawValue::Text(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+impl From<RawValue> for serde_json::Value {
+    fn from(x: RawValue) -> Self {
+        match x {
+            RawValue::I64(x) => serde_json::Value::from(x),
+            RawValue::U64(x) => serde_json::Value::from(x),
+            // Integral values are emitted without fraction
+            RawValue::Number(x) if x.fract() == 0.0 && x.abs() < 2f64.powi(53) => {
+                serde_json::Value::from(x as i64)
+            }
+            RawValue::Number(x) => serde_json::Number::from_f64(x)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            RawValue::Text(x) => serde_json::Value::from(x),
+        }
+    }
+}
+
+pub trait ToRawValue {
+    fn to_raw_value(&self) -> Option<RawValue>;
+}
+
+// `None` is stored if the value is unavailable, and it is null in JSON
+impl<T: ToRawValue> ToRawValue for Option<T> {
+    fn to_raw_value(&self) -> Option<RawValue> {
+        self.as_ref().and_then(ToRawValue::to_raw_value)
+    }
+}
+
+macro_rules! impl_to_raw_value_number {
+    ($variant:ident, $($x:ty),*) => {
+        $(
+            impl ToRawValue for $x {
+                fn to_raw_value(&self) -> Option<RawValue> {
+                    Some(RawValue::$variant((*self).into()))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_raw_value_number!(I64, i32, i64);
+impl_to_raw_value_number!(U64, u32, u64);
+
+impl ToRawValue for String {
+    fn to_raw_value(&self) -> Option<RawValue> {
+        Some(RawValue::Text(self.clone()))
+    }
+}
+
+impl ToRawValue for Duration {
+    fn to_raw_value(&self) -> Option<RawValue> {
+        Some(RawValue::Number(self.num_milliseconds() as f64 / 1000.0))
+    }
+}
+
+impl ToRawValue for DateTime<Local> {
+    fn to_raw_value(&self) -> Option<RawValue> {
+        Some(RawValue::Number(self.timestamp_millis() as f64 / 1000.0))
+    }
+}
 
 pub trait Column {
     fn add(&mut self, proc: &ProcessInfo);
@@ -12,6 +108,14 @@ pub trait Column {
         true
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::default()
+    }
+
+    fn set_mode(&mut self, mode: &ConfigColumnMode) -> Result<(), Error> {
+        bail!("mode \"{:?}\" is not supported", mode)
+    }
+
     fn display_header(
         &self,
         align: &ConfigColumnAlign,
@@ -20,9 +124,11 @@ pub trait Column {
     ) -> String;
     fn display_unit(&self, align: &ConfigColumnAlign) -> String;
     fn display_content(&self, pid: i32, align: &ConfigColumnAlign) -> Option<String>;
-    fn display_json(&self, pid: i32) -> String;
+    fn display_raw(&self, pid: i32) -> Option<String>;
+    fn display_json(&self, pid: i32) -> serde_json::Value;
     fn find_partial(&self, pid: i32, keyword: &str, content_to_lowercase: bool) -> bool;
     fn find_exact(&self, pid: i32, keyword: &str, content_to_lowercase: bool) -> bool;
+    fn raw_value(&self, pid: i32) -> Option<RawValue>;
     fn sorted_pid(&self, order: &ConfigSortOrder) -> Vec<i32>;
     fn apply_visible(&mut self, visible_pids: &[i32]);
     fn reset_width(
@@ -35,6 +141,7 @@ pub trait Column {
     fn update_width(&mut self, pid: i32, max_width: Option<usize>);
     fn get_width(&self) -> usize;
     fn is_numeric(&self) -> bool;
+    fn header(&self) -> &str;
 }
 
 #[macro_export]
@@ -87,26 +194,29 @@ macro_rules! column_default_display_content {
     };
 }
 
+#[macro_export]
+macro_rules! column_default_display_raw {
+    () => {
+        fn display_raw(&self, pid: i32) -> Option<String> {
+            self.fmt_contents.get(&pid).cloned()
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! column_default_display_json {
     () => {
-        fn display_json(&self, pid: i32) -> String {
-            let value = if self.is_numeric() {
-                self.raw_contents
-                    .get(&pid)
-                    .map(|x| x.to_string())
-                    .unwrap_or("".to_string())
+        fn display_json(&self, pid: i32) -> serde_json::Value {
+            if self.is_numeric() {
+                self.raw_value(pid)
+                    .map(serde_json::Value::from)
+                    .unwrap_or(serde_json::Value::Null)
             } else {
-                let value = self
-                    .fmt_contents
+                self.fmt_contents
                     .get(&pid)
-                    .map(|x| x.clone())
-                    .unwrap_or("".to_string());
-                let value = value.replace("\\", "\\\\");
-                let value = value.replace("\"", "\\\"");
-                format!("\"{}\"", value)
-            };
-            format!("\"{}\": {}", self.header, value)
+                    .map(|x| serde_json::Value::from(x.as_str()))
+                    .unwrap_or(serde_json::Value::Null)
+            }
         }
     };
 }
@@ -145,6 +255,24 @@ macro_rules! column_default_find_exact {
     };
 }
 
+#[macro_export]
+macro_rules! column_default_raw_value {
+    () => {
+        fn raw_value(&self, pid: i32) -> Option<$crate::column::RawValue> {
+            self.raw_contents
+                .get(&pid)
+                .and_then($crate::column::ToRawValue::to_raw_value)
+        }
+    };
+    ($scale:expr) => {
+        fn raw_value(&self, pid: i32) -> Option<$crate::column::RawValue> {
+            self.raw_contents
+                .get(&pid)
+                .map(|x| $crate::column::RawValue::Number(*x as f64 / $scale))
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! column_default_sorted_pid {
     ($x:ty) => {
@@ -236,20 +364,76 @@ macro_rules! column_default_is_numeric {
     };
 }
 
+#[macro_export]
+macro_rules! column_default_header {
+    () => {
+        fn header(&self) -> &str {
+            &self.header
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! column_default {
     ($x:ty, $y:expr) => {
         $crate::column_default_display_header!();
         $crate::column_default_display_unit!();
         $crate::column_default_display_content!();
+        $crate::column_default_display_raw!();
+        $crate::column_default_display_json!();
+        $crate::column_default_find_partial!();
+        $crate::column_default_find_exact!();
+        $crate::column_default_raw_value!();
+        $crate::column_default_sorted_pid!($x);
+        $crate::column_default_apply_visible!();
+        $crate::column_default_reset_width!();
+        $crate::column_default_update_width!();
+        $crate::column_default_get_width!();
+        $crate::column_default_is_numeric!($y);
+        $crate::column_default_header!();
+    };
+    ($x:ty, $y:expr, $scale:expr) => {
+        $crate::column_default_display_header!();
+        $crate::column_default_display_unit!();
+        $crate::column_default_display_content!();
+        $crate::column_default_display_raw!();
         $crate::column_default_display_json!();
         $crate::column_default_find_partial!();
         $crate::column_default_find_exact!();
+        $crate::column_default_raw_value!($scale);
         $crate::column_default_sorted_pid!($x);
         $crate::column_default_apply_visible!();
         $crate::column_default_reset_width!();
         $crate::column_default_update_width!();
         $crate::column_default_get_width!();
         $crate::column_default_is_numeric!($y);
+        $crate::column_default_header!();
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_value() {
+        // Integers are exact even above 2^53
+        let x = u64::MAX.to_raw_value().unwrap();
+        assert_eq!(x.to_string(), "18446744073709551615");
+        assert_eq!(
+            serde_json::Value::from(x),
+            serde_json::Value::from(u64::MAX)
+        );
+        let x = (-1i32).to_raw_value().unwrap();
+        assert_eq!(x.to_string(), "-1");
+        assert_eq!(serde_json::Value::from(x), serde_json::Value::from(-1));
+
+        let x = Duration::milliseconds(1500).to_raw_value().unwrap();
+        assert_eq!(x.to_string(), "1.5");
+        assert_eq!(x.as_f64(), Some(1.5));
+        assert_eq!(RawValue::Text(String::from("a")).as_f64(), None);
+
+        assert_eq!(Some(1u64).to_raw_value(), Some(RawValue::U64(1)));
+        assert_eq!(None::<u64>.to_raw_value(), None);
+    }
+}
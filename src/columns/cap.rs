@@ -0,0 +1,160 @@
+use crate::Column;
+use crate::capability::CapSet;
+use crate::config::ConfigColumnMode;
+use crate::process::{DataSource, ProcessInfo};
+use anyhow::{Error, bail};
+use std::cmp;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub struct Cap {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, Option<u64>>,
+    names: HashMap<i32, Option<Vec<String>>>,
+    width: usize,
+    mode: ConfigColumnMode,
+    last_cap: u32,
+    set: CapSet,
+}
+
+impl Cap {
+    pub fn new(header: Option<String>, procfs: Option<PathBuf>, set: CapSet) -> Self {
+        let header = header.unwrap_or_else(|| String::from(set.header()));
+        let unit = String::new();
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            names: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+            mode: ConfigColumnMode::Name,
+            last_cap: crate::capability::last_cap(&procfs),
+            set,
+        }
+    }
+}
+
+impl Column for Cap {
+    fn add(&mut self, proc: &ProcessInfo) {
+        // Unreadable capabilities are null instead of no capability
+        let mask = proc.curr_status.as_ref().and_then(|x| self.set.mask(x));
+        let fmt_content = mask
+            .map(|x| crate::capability::format(x, self.last_cap, self.set, &self.mode))
+            .unwrap_or_default();
+        let raw_content = mask;
+        let names = mask.map(crate::capability::names);
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+        self.names.insert(proc.pid, names);
+    }
+
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
+    fn set_mode(&mut self, mode: &ConfigColumnMode) -> Result<(), Error> {
+        match mode {
+            ConfigColumnMode::Name | ConfigColumnMode::Compact | ConfigColumnMode::Hex => {
+                self.mode = mode.clone()
+            }
+            _ => bail!("mode \"{:?}\" is not supported", mode),
+        }
+        Ok(())
+    }
+
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        if self.is_numeric() {
+            self.raw_value(pid)
+                .map(serde_json::Value::from)
+                .unwrap_or(serde_json::Value::Null)
+        } else {
+            self.names
+                .get(&pid)
+                .and_then(|x| x.as_ref())
+                .map(|x| serde_json::Value::from(x.as_slice()))
+                .unwrap_or(serde_json::Value::Null)
+        }
+    }
+
+    // Capability names are searchable in all modes
+    fn find_partial(&self, pid: i32, keyword: &str, content_to_lowercase: bool) -> bool {
+        self.names
+            .get(&pid)
+            .and_then(|x| x.as_ref())
+            .map(|x| {
+                x.iter().any(|x| {
+                    if content_to_lowercase {
+                        x.to_ascii_lowercase().contains(keyword)
+                    } else {
+                        x.contains(keyword)
+                    }
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    fn find_exact(&self, pid: i32, keyword: &str, content_to_lowercase: bool) -> bool {
+        self.names
+            .get(&pid)
+            .and_then(|x| x.as_ref())
+            .map(|x| {
+                x.iter().any(|x| {
+                    if content_to_lowercase {
+                        x.eq_ignore_ascii_case(keyword)
+                    } else {
+                        x == keyword
+                    }
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    fn is_numeric(&self) -> bool {
+        self.mode == ConfigColumnMode::Hex
+    }
+
+    crate::column_default_display_header!();
+    crate::column_default_display_unit!();
+    crate::column_default_display_content!();
+    crate::column_default_display_raw!();
+    crate::column_default_raw_value!();
+    crate::column_default_sorted_pid!(Option<u64>);
+    crate::column_default_apply_visible!();
+    crate::column_default_reset_width!();
+    crate::column_default_update_width!();
+    crate::column_default_get_width!();
+    crate::column_default_header!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use procfs::process::Process;
+
+    #[test]
+    fn test_cap() {
+        let mut status = Process::myself().unwrap().status().unwrap();
+        status.capbnd = Some(1 << 12);
+        let mut column = Cap::new(None, None, CapSet::Bnd);
+        column.add(&ProcessInfo {
+            curr_status: Some(status),
+            ..ProcessInfo::for_test(1)
+        });
+        column.add(&ProcessInfo::for_test(2));
+
+        assert_eq!(column.display_json(1), serde_json::json!(["cap_net_admin"]));
+        assert!(column.find_partial(1, "net_admin", true));
+        assert!(column.find_exact(1, "cap_net_admin", true));
+        // Unreadable status is null instead of no capability
+        assert_eq!(column.display_json(2), serde_json::Value::Null);
+        assert!(!column.find_partial(2, "", true));
+
+        column.set_mode(&ConfigColumnMode::Hex).unwrap();
+        assert_eq!(column.display_json(1), serde_json::Value::from(1u64 << 12));
+        assert_eq!(column.display_json(2), serde_json::Value::Null);
+    }
+}
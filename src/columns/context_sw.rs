@@ -1,6 +1,8 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::process::DataSource;
 use crate::process::ProcessInfo;
 use crate::util::bytify;
-use crate::{column_default, Column};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -33,8 +35,7 @@ impl Column for ContextSw {
             if let Some(voluntary_ctxt_switches) = status.voluntary_ctxt_switches
                 && let Some(nonvoluntary_ctxt_switches) = status.nonvoluntary_ctxt_switches
             {
-                let sw = voluntary_ctxt_switches
-                    + nonvoluntary_ctxt_switches;
+                let sw = voluntary_ctxt_switches + nonvoluntary_ctxt_switches;
                 (bytify(sw), sw)
             } else {
                 (String::new(), 0)
@@ -47,6 +48,10 @@ impl Column for ContextSw {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
     column_default!(u64, true);
 }
 
@@ -1,5 +1,6 @@
 use crate::process::ProcessInfo;
-use crate::{column_default, util, Column};
+use crate::util::SystemState;
+use crate::{Column, column_default, util};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -9,10 +10,16 @@ pub struct CpuTime {
     fmt_contents: HashMap<i32, String>,
     raw_contents: HashMap<i32, u64>,
     width: usize,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    ticks_per_second: u64,
 }
 
 impl CpuTime {
-    pub fn new(header: Option<String>) -> Self {
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "android")),
+        allow(unused_variables)
+    )]
+    pub fn new(header: Option<String>, system_state: SystemState) -> Self {
         let header = header.unwrap_or_else(|| String::from("CPU Time"));
         let unit = String::new();
         Self {
@@ -21,6 +28,8 @@ impl CpuTime {
             width: 0,
             header,
             unit,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            ticks_per_second: system_state.ticks_per_second,
         }
     }
 }
@@ -28,8 +37,8 @@ impl CpuTime {
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl Column for CpuTime {
     fn add(&mut self, proc: &ProcessInfo) {
-        let time_sec = (proc.curr_proc.stat().utime + proc.curr_proc.stat().stime)
-            / procfs::ticks_per_second();
+        let time_sec =
+            (proc.curr_proc.stat().utime + proc.curr_proc.stat().stime) / self.ticks_per_second;
 
         let fmt_content = util::parse_time(time_sec);
         let raw_content = time_sec;
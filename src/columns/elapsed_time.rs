@@ -1,18 +1,14 @@
 use crate::process::ProcessInfo;
-use crate::{column_default, Column};
-#[cfg(not(target_os = "windows"))]
-use chrono::offset::TimeZone;
+use crate::util::SystemState;
+use crate::{Column, column_default};
 #[cfg(any(target_os = "linux", target_os = "android"))]
 use chrono::DateTime;
+#[cfg(not(target_os = "windows"))]
+use chrono::offset::TimeZone;
 use chrono::{Duration, Local};
-#[cfg(any(target_os = "linux", target_os = "android"))]
-use once_cell::sync::Lazy;
 use std::cmp;
 use std::collections::HashMap;
 
-#[cfg(any(target_os = "linux", target_os = "android"))]
-static TICKS_PER_SECOND: Lazy<u64> = Lazy::new(procfs::ticks_per_second);
-
 pub struct ElapsedTime {
     header: String,
     unit: String,
@@ -21,10 +17,18 @@ pub struct ElapsedTime {
     width: usize,
     #[cfg(any(target_os = "linux", target_os = "android"))]
     boot_time: DateTime<Local>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    ticks_per_second: u64,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    now: DateTime<Local>,
 }
 
 impl ElapsedTime {
-    pub fn new(header: Option<String>) -> Self {
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "android")),
+        allow(unused_variables)
+    )]
+    pub fn new(header: Option<String>, system_state: SystemState) -> Self {
         let header = header.unwrap_or_else(|| String::from("Elapsed"));
         let unit = String::new();
         Self {
@@ -34,7 +38,11 @@ impl ElapsedTime {
             header,
             unit,
             #[cfg(any(target_os = "linux", target_os = "android"))]
-            boot_time: procfs::boot_time().unwrap_or_else(|_| Local.timestamp_opt(0, 0).unwrap()),
+            boot_time: Local.timestamp_opt(system_state.boot_time, 0).unwrap(),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            ticks_per_second: system_state.ticks_per_second,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            now: Local.timestamp_millis_opt(system_state.time).unwrap(),
         }
     }
 }
@@ -66,10 +74,10 @@ fn format_duration(duration: Duration) -> String {
 impl Column for ElapsedTime {
     fn add(&mut self, proc: &ProcessInfo) {
         let starttime = proc.curr_proc.stat().starttime;
-        let seconds_since_boot = starttime as f32 / *TICKS_PER_SECOND as f32;
+        let seconds_since_boot = starttime as f32 / self.ticks_per_second as f32;
         let start_time = self.boot_time
             + Duration::try_milliseconds((seconds_since_boot * 1000.0) as i64).unwrap_or_default();
-        let raw_content = Local::now().signed_duration_since(start_time);
+        let raw_content = self.now.signed_duration_since(start_time);
         let fmt_content = format_duration(raw_content);
 
         self.fmt_contents.insert(proc.pid, fmt_content);
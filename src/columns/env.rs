@@ -33,7 +33,8 @@ impl Env {
 impl Column for Env {
     fn add(&mut self, proc: &ProcessInfo) {
         let mut fmt_content = String::new();
-        if let Ok(proc) = crate::util::process_new(proc.pid, &self.procfs)
+        if !proc.curr_proc.is_record()
+            && let Ok(proc) = crate::util::process_new(proc.pid, &self.procfs)
             && let Ok(envs) = proc.environ()
         {
             for (k, v) in envs {
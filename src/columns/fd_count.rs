@@ -0,0 +1,66 @@
+use crate::Column;
+use crate::process::{DataSource, ProcessInfo};
+use std::cmp;
+use std::collections::HashMap;
+
+pub struct FdCount {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, u64>,
+    width: usize,
+}
+
+impl FdCount {
+    pub fn new(header: Option<String>) -> Self {
+        let header = header.unwrap_or_else(|| String::from("FDs"));
+        let unit = String::new();
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+        }
+    }
+}
+
+impl Column for FdCount {
+    fn add(&mut self, proc: &ProcessInfo) {
+        let (raw_content, fmt_content) = if let Some(count) = proc.curr_fd_count {
+            (count as u64, format!("{count}"))
+        } else {
+            (0, String::new())
+        };
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+    }
+
+    fn data_source(&self) -> DataSource {
+        DataSource::FD
+    }
+
+    // FD count which can't be read is null
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        match self.raw_value(pid) {
+            Some(x) if !self.fmt_contents[&pid].is_empty() => serde_json::Value::from(x),
+            _ => serde_json::Value::Null,
+        }
+    }
+
+    crate::column_default_display_header!();
+    crate::column_default_display_unit!();
+    crate::column_default_display_content!();
+    crate::column_default_display_raw!();
+    crate::column_default_find_partial!();
+    crate::column_default_find_exact!();
+    crate::column_default_raw_value!();
+    crate::column_default_sorted_pid!(u64);
+    crate::column_default_apply_visible!();
+    crate::column_default_reset_width!();
+    crate::column_default_update_width!();
+    crate::column_default_get_width!();
+    crate::column_default_is_numeric!(true);
+    crate::column_default_header!();
+}
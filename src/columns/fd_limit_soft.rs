@@ -0,0 +1,67 @@
+use crate::Column;
+use crate::process::ProcessInfo;
+use procfs::process::LimitValue;
+use std::cmp;
+use std::collections::HashMap;
+
+pub struct FdLimitSoft {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, u64>,
+    width: usize,
+}
+
+impl FdLimitSoft {
+    pub fn new(header: Option<String>) -> Self {
+        let header = header.unwrap_or_else(|| String::from("FD Soft"));
+        let unit = String::new();
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+        }
+    }
+}
+
+impl Column for FdLimitSoft {
+    fn add(&mut self, proc: &ProcessInfo) {
+        // Unlimited is sorted as the largest value
+        let (raw_content, fmt_content) = if let Ok(limit) = proc.curr_proc.max_open_files() {
+            match limit.soft_limit {
+                LimitValue::Value(x) => (x, format!("{x}")),
+                LimitValue::Unlimited => (u64::MAX, String::from("unlimited")),
+            }
+        } else {
+            (0, String::new())
+        };
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+    }
+
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        match self.raw_contents.get(&pid) {
+            Some(&u64::MAX) => serde_json::Value::from("unlimited"),
+            Some(x) if !self.fmt_contents[&pid].is_empty() => serde_json::Value::from(*x),
+            _ => serde_json::Value::Null,
+        }
+    }
+
+    crate::column_default_display_header!();
+    crate::column_default_display_unit!();
+    crate::column_default_display_content!();
+    crate::column_default_display_raw!();
+    crate::column_default_find_partial!();
+    crate::column_default_find_exact!();
+    crate::column_default_raw_value!();
+    crate::column_default_sorted_pid!(u64);
+    crate::column_default_apply_visible!();
+    crate::column_default_reset_width!();
+    crate::column_default_update_width!();
+    crate::column_default_get_width!();
+    crate::column_default_is_numeric!(true);
+    crate::column_default_header!();
+}
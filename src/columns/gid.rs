@@ -1,7 +1,9 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::process::DataSource;
 use crate::process::ProcessInfo;
 #[cfg(target_os = "windows")]
 use crate::util::format_sid;
-use crate::{column_default, Column};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -44,6 +46,10 @@ impl Column for Gid {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
     column_default!(u32, true);
 }
 
@@ -1,5 +1,5 @@
-use crate::process::ProcessInfo;
-use crate::{column_default, Column};
+use crate::process::{DataSource, ProcessInfo};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -38,5 +38,9 @@ impl Column for GidFs {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
     column_default!(u32, true);
 }
@@ -1,5 +1,7 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::process::DataSource;
 use crate::process::ProcessInfo;
-use crate::{column_default, Column};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -39,6 +41,10 @@ impl Column for GidReal {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
     column_default!(u32, true);
 }
 
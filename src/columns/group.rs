@@ -1,9 +1,11 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::process::DataSource;
 use crate::process::ProcessInfo;
-#[cfg(target_os = "windows")]
-use crate::util::format_sid;
 #[cfg(not(target_os = "windows"))]
 use crate::util::USERS_CACHE;
-use crate::{column_default, Column};
+#[cfg(target_os = "windows")]
+use crate::util::format_sid;
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 #[cfg(not(target_os = "windows"))]
@@ -53,6 +55,10 @@ impl Column for Group {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
     column_default!(String, false);
 }
 
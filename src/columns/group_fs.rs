@@ -1,6 +1,6 @@
-use crate::process::ProcessInfo;
+use crate::process::{DataSource, ProcessInfo};
 use crate::util::USERS_CACHE;
-use crate::{column_default, Column};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 use uzers::Groups;
@@ -45,5 +45,9 @@ impl Column for GroupFs {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
     column_default!(String, false);
 }
@@ -0,0 +1,154 @@
+use crate::Column;
+use crate::config::ConfigColumnMode;
+use crate::process::{DataSource, ProcessInfo};
+use anyhow::{Error, bail};
+use std::cmp;
+use std::collections::HashMap;
+
+pub struct InnerPid {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, Option<i32>>,
+    chains: HashMap<i32, Vec<i32>>,
+    width: usize,
+    list: bool,
+}
+
+impl InnerPid {
+    pub fn new(header: Option<String>) -> Self {
+        let header = header.unwrap_or_else(|| String::from("Inner PID"));
+        let unit = String::new();
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            chains: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+            list: false,
+        }
+    }
+}
+
+impl Column for InnerPid {
+    fn add(&mut self, proc: &ProcessInfo) {
+        // NSpid is listed from the outermost PID namespace to the innermost one
+        let chain = proc
+            .curr_status
+            .as_ref()
+            .and_then(|x| x.nspid.clone())
+            .unwrap_or_default();
+
+        // NSpid which is missing on old kernels or unreadable is null
+        let (fmt_content, raw_content) = if let Some(inner) = chain.last() {
+            let fmt_content = if self.list {
+                chain
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            } else {
+                format!("{inner}")
+            };
+            (fmt_content, Some(*inner))
+        } else {
+            (String::new(), None)
+        };
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+        self.chains.insert(proc.pid, chain);
+    }
+
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
+    fn set_mode(&mut self, mode: &ConfigColumnMode) -> Result<(), Error> {
+        match mode {
+            ConfigColumnMode::List => self.list = true,
+            _ => bail!("mode \"{:?}\" is not supported", mode),
+        }
+        Ok(())
+    }
+
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        if self.list {
+            self.chains
+                .get(&pid)
+                .filter(|x| !x.is_empty())
+                .map(|x| serde_json::Value::from(x.as_slice()))
+                .unwrap_or(serde_json::Value::Null)
+        } else {
+            self.raw_value(pid)
+                .map(serde_json::Value::from)
+                .unwrap_or(serde_json::Value::Null)
+        }
+    }
+
+    fn find_partial(&self, pid: i32, keyword: &str, _content_to_lowercase: bool) -> bool {
+        self.chains
+            .get(&pid)
+            .map(|x| x.iter().any(|x| x.to_string().contains(keyword)))
+            .unwrap_or(false)
+    }
+
+    // Both the host PID and PIDs in nested namespaces are matched
+    fn find_exact(&self, pid: i32, keyword: &str, _content_to_lowercase: bool) -> bool {
+        self.chains
+            .get(&pid)
+            .map(|x| x.iter().any(|x| x.to_string() == keyword))
+            .unwrap_or(false)
+    }
+
+    fn is_numeric(&self) -> bool {
+        !self.list
+    }
+
+    crate::column_default_display_header!();
+    crate::column_default_display_unit!();
+    crate::column_default_display_content!();
+    crate::column_default_display_raw!();
+    crate::column_default_raw_value!();
+    crate::column_default_sorted_pid!(Option<i32>);
+    crate::column_default_apply_visible!();
+    crate::column_default_reset_width!();
+    crate::column_default_update_width!();
+    crate::column_default_get_width!();
+    crate::column_default_header!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use procfs::process::{Process, Status};
+
+    fn proc_with_status(pid: i32, status: Option<Status>) -> ProcessInfo {
+        ProcessInfo {
+            curr_status: status,
+            ..ProcessInfo::for_test(pid)
+        }
+    }
+
+    #[test]
+    fn test_inner_pid() {
+        let status = Process::myself().unwrap().status().unwrap();
+        let inner = *status.nspid.as_ref().unwrap().last().unwrap();
+        let mut nspid_missing = status.clone();
+        nspid_missing.nspid = None;
+
+        let mut column = InnerPid::new(None);
+        column.add(&proc_with_status(1, Some(status)));
+        column.add(&proc_with_status(2, Some(nspid_missing)));
+        column.add(&proc_with_status(3, None));
+
+        assert_eq!(column.display_json(1), serde_json::Value::from(inner));
+        assert_eq!(column.display_json(2), serde_json::Value::Null);
+        assert_eq!(column.display_json(3), serde_json::Value::Null);
+
+        column.set_mode(&ConfigColumnMode::List).unwrap();
+        assert!(column.display_json(1).is_array());
+        assert_eq!(column.display_json(2), serde_json::Value::Null);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::process::DataSource;
 use crate::process::ProcessInfo;
 use crate::util::bytify;
 use crate::{Column, column_default};
@@ -44,6 +46,10 @@ impl Column for InvoluntaryContextSw {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
     column_default!(u64, true);
 }
 
@@ -0,0 +1,71 @@
+use crate::process::ProcessInfo;
+use crate::{Column, column_default};
+use std::cmp;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub struct Ns {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, Option<u64>>,
+    width: usize,
+    procfs: Option<PathBuf>,
+    ns: &'static str,
+}
+
+impl Ns {
+    /// `ns` is a file name in `/proc/<pid>/ns` like `pid` or `net`
+    pub fn new(header: Option<String>, procfs: Option<PathBuf>, ns: &'static str) -> Self {
+        let header = header.unwrap_or_else(|| format!("{} NS", ns.to_ascii_uppercase()));
+        let unit = String::new();
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+            procfs,
+            ns,
+        }
+    }
+}
+
+impl Column for Ns {
+    fn add(&mut self, proc: &ProcessInfo) {
+        // Namespaces of recorded processes are unknown
+        let inode = if proc.curr_proc.is_record() {
+            None
+        } else {
+            crate::util::ns_inode(proc.pid, &self.procfs, self.ns)
+        };
+        // Namespace which can't be read is null because 0 is not a valid inode
+        let raw_content = inode;
+        let fmt_content = inode.map(|x| format!("{x}")).unwrap_or_default();
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+    }
+
+    column_default!(Option<u64>, true);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ns() {
+        let pid = std::process::id() as i32;
+        let mut ns = Ns::new(None, None, "net");
+        ns.add(&ProcessInfo::for_test(pid));
+        // PID which doesn't exist
+        ns.add(&ProcessInfo::for_test(i32::MAX));
+
+        let inode = crate::util::ns_inode(pid, &None, "net").unwrap();
+        assert_eq!(ns.display_json(pid), serde_json::Value::from(inode));
+        assert_eq!(ns.fmt_contents[&pid], inode.to_string());
+        assert_eq!(ns.display_json(i32::MAX), serde_json::Value::Null);
+        assert_eq!(ns.fmt_contents[&i32::MAX], "");
+    }
+}
@@ -0,0 +1,112 @@
+use crate::Column;
+use crate::process::ProcessInfo;
+use std::cmp;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const NAMESPACES: [&str; 8] = ["pid", "net", "mnt", "user", "uts", "ipc", "cgroup", "time"];
+
+pub struct NsNonHost {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, Option<String>>,
+    width: usize,
+    procfs: Option<PathBuf>,
+    host: Vec<Option<u64>>,
+}
+
+impl NsNonHost {
+    pub fn new(header: Option<String>, procfs: Option<PathBuf>) -> Self {
+        let header = header.unwrap_or_else(|| String::from("Non-host NS"));
+        let unit = String::new();
+        let host = NAMESPACES
+            .iter()
+            .map(|ns| crate::util::ns_inode(1, &procfs, ns))
+            .collect();
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+            procfs,
+            host,
+        }
+    }
+}
+
+impl Column for NsNonHost {
+    fn add(&mut self, proc: &ProcessInfo) {
+        // Namespaces which can't be read from the process or PID 1 are not compared
+        let mut non_host = Vec::new();
+        let mut compared = false;
+        // Namespaces of recorded processes are unknown
+        if !proc.curr_proc.is_record() {
+            for (ns, host) in NAMESPACES.iter().zip(&self.host) {
+                if let Some(host) = host
+                    && let Some(inode) = crate::util::ns_inode(proc.pid, &self.procfs, ns)
+                {
+                    compared = true;
+                    if inode != *host {
+                        non_host.push(*ns);
+                    }
+                }
+            }
+        }
+
+        // Unknown namespaces are `?` and null to be distinguished from the host namespaces
+        let raw_content = compared.then(|| non_host.join(","));
+        let fmt_content = raw_content.clone().unwrap_or_else(|| String::from("?"));
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+    }
+
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        self.raw_contents
+            .get(&pid)
+            .and_then(|x| x.as_deref())
+            .map(serde_json::Value::from)
+            .unwrap_or(serde_json::Value::Null)
+    }
+
+    crate::column_default_display_header!();
+    crate::column_default_display_unit!();
+    crate::column_default_display_content!();
+    crate::column_default_display_raw!();
+    crate::column_default_find_partial!();
+    crate::column_default_find_exact!();
+    crate::column_default_raw_value!();
+    crate::column_default_sorted_pid!(Option<String>);
+    crate::column_default_apply_visible!();
+    crate::column_default_reset_width!();
+    crate::column_default_update_width!();
+    crate::column_default_get_width!();
+    crate::column_default_is_numeric!(false);
+    crate::column_default_header!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ns_non_host() {
+        let pid = std::process::id() as i32;
+        let mut column = NsNonHost::new(None, None);
+        // Compare with the namespaces of this process instead of PID 1
+        column.host = NAMESPACES
+            .iter()
+            .map(|ns| crate::util::ns_inode(pid, &None, ns))
+            .collect();
+        column.add(&ProcessInfo::for_test(pid));
+        // PID which doesn't exist
+        column.add(&ProcessInfo::for_test(i32::MAX));
+
+        assert_eq!(column.fmt_contents[&pid], "");
+        assert_eq!(column.display_json(pid), serde_json::Value::from(""));
+        assert_eq!(column.fmt_contents[&i32::MAX], "?");
+        assert_eq!(column.display_json(i32::MAX), serde_json::Value::Null);
+    }
+}
@@ -23,10 +23,7 @@ pub mod processor;
 pub mod read_bytes;
 pub mod separator;
 pub mod session;
-pub mod shd_pnd;
-pub mod sig_blk;
-pub mod sig_cgt;
-pub mod sig_ign;
+pub mod sig;
 pub mod slot;
 pub mod start_time;
 pub mod state;
@@ -76,10 +73,7 @@ pub use self::processor::Processor;
 pub use self::read_bytes::ReadBytes;
 pub use self::separator::Separator;
 pub use self::session::Session;
-pub use self::shd_pnd::ShdPnd;
-pub use self::sig_blk::SigBlk;
-pub use self::sig_cgt::SigCgt;
-pub use self::sig_ign::SigIgn;
+pub use self::sig::{Sig, SigSet};
 pub use self::slot::Slot;
 pub use self::start_time::StartTime;
 pub use self::state::State;
@@ -105,6 +99,7 @@ pub use self::wchan::Wchan;
 pub use self::write_bytes::WriteBytes;
 
 use crate::column::Column;
+use crate::util::SystemState;
 use once_cell::sync::Lazy;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -174,6 +169,7 @@ pub enum ConfigColumnKind {
 // gen_column
 // ---------------------------------------------------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 pub fn gen_column(
     kind: &ConfigColumnKind,
     header: Option<String>,
@@ -182,12 +178,13 @@ pub fn gen_column(
     abbr_sid: bool,
     tree_symbols: &[String; 5],
     procfs: Option<PathBuf>,
+    system_state: SystemState,
 ) -> Box<dyn Column> {
     match kind {
         ConfigColumnKind::Command => Box::new(Command::new(header)),
         ConfigColumnKind::ContextSw => Box::new(ContextSw::new(header)),
-        ConfigColumnKind::CpuTime => Box::new(CpuTime::new(header)),
-        ConfigColumnKind::ElapsedTime => Box::new(ElapsedTime::new(header)),
+        ConfigColumnKind::CpuTime => Box::new(CpuTime::new(header, system_state)),
+        ConfigColumnKind::ElapsedTime => Box::new(ElapsedTime::new(header, system_state)),
         ConfigColumnKind::Empty => Box::new(Empty::new()),
         ConfigColumnKind::Env => Box::new(Env::new(header, procfs)),
         ConfigColumnKind::FileName => Box::new(FileName::new(header)),
@@ -209,12 +206,12 @@ pub fn gen_column(
         ConfigColumnKind::ReadBytes => Box::new(ReadBytes::new(header)),
         ConfigColumnKind::Separator => Box::new(Separator::new(separator)),
         ConfigColumnKind::Session => Box::new(Session::new(header)),
-        ConfigColumnKind::ShdPnd => Box::new(ShdPnd::new(header)),
+        ConfigColumnKind::ShdPnd => Box::new(Sig::new(header, SigSet::ShdPnd)),
         ConfigColumnKind::Slot => Box::new(Slot::new()),
-        ConfigColumnKind::SigBlk => Box::new(SigBlk::new(header)),
-        ConfigColumnKind::SigCgt => Box::new(SigCgt::new(header)),
-        ConfigColumnKind::SigIgn => Box::new(SigIgn::new(header)),
-        ConfigColumnKind::StartTime => Box::new(StartTime::new(header)),
+        ConfigColumnKind::SigBlk => Box::new(Sig::new(header, SigSet::Blk)),
+        ConfigColumnKind::SigCgt => Box::new(Sig::new(header, SigSet::Cgt)),
+        ConfigColumnKind::SigIgn => Box::new(Sig::new(header, SigSet::Ign)),
+        ConfigColumnKind::StartTime => Box::new(StartTime::new(header, system_state)),
         ConfigColumnKind::State => Box::new(State::new(header)),
         ConfigColumnKind::Threads => Box::new(Threads::new(header)),
         ConfigColumnKind::Tree => Box::new(Tree::new(tree_symbols)),
@@ -223,15 +220,15 @@ pub fn gen_column(
         ConfigColumnKind::Uid => Box::new(Uid::new(header, abbr_sid)),
         ConfigColumnKind::UidReal => Box::new(UidReal::new(header)),
         ConfigColumnKind::UidSaved => Box::new(UidSaved::new(header)),
-        ConfigColumnKind::UsageCpu => Box::new(UsageCpu::new(header)),
-        ConfigColumnKind::UsageMem => Box::new(UsageMem::new(header)),
+        ConfigColumnKind::UsageCpu => Box::new(UsageCpu::new(header, system_state)),
+        ConfigColumnKind::UsageMem => Box::new(UsageMem::new(header, system_state)),
         ConfigColumnKind::User => Box::new(User::new(header, abbr_sid)),
         ConfigColumnKind::UserReal => Box::new(UserReal::new(header)),
         ConfigColumnKind::UserSaved => Box::new(UserSaved::new(header)),
         ConfigColumnKind::VmData => Box::new(VmData::new(header)),
         ConfigColumnKind::VmExe => Box::new(VmExe::new(header)),
         ConfigColumnKind::VmHwm => Box::new(VmHwm::new(header)),
-        ConfigColumnKind::VmRss => Box::new(VmRss::new(header)),
+        ConfigColumnKind::VmRss => Box::new(VmRss::new(header, system_state)),
         ConfigColumnKind::VmSize => Box::new(VmSize::new(header)),
         ConfigColumnKind::VmStack => Box::new(VmStack::new(header)),
         ConfigColumnKind::Wchan => Box::new(Wchan::new(header)),
@@ -1,9 +1,8 @@
+pub mod cap;
 pub mod ccgroup;
 pub mod cgroup;
 pub mod command;
 pub mod context_sw;
-pub mod voluntary_context_sw;
-pub mod involuntary_context_sw;
 pub mod cpu_time;
 #[cfg(feature = "docker")]
 pub mod docker;
@@ -12,6 +11,9 @@ pub mod elapsed_time;
 pub mod empty;
 pub mod env;
 pub mod esp;
+pub mod fd_count;
+pub mod fd_limit_hard;
+pub mod fd_limit_soft;
 pub mod file_name;
 pub mod gid;
 pub mod gid_fs;
@@ -21,10 +23,14 @@ pub mod group;
 pub mod group_fs;
 pub mod group_real;
 pub mod group_saved;
+pub mod inner_pid;
+pub mod involuntary_context_sw;
 pub mod maj_flt;
 pub mod min_flt;
 pub mod multi_slot;
 pub mod nice;
+pub mod ns;
+pub mod ns_non_host;
 pub mod pgid;
 pub mod pid;
 pub mod policy;
@@ -36,15 +42,13 @@ pub mod rt_priority;
 pub mod sec_context;
 pub mod separator;
 pub mod session;
-pub mod shd_pnd;
-pub mod sig_blk;
-pub mod sig_cgt;
-pub mod sig_ign;
-pub mod sig_pnd;
+pub mod sig;
 pub mod slot;
+pub mod smaps;
 pub mod ssb;
 pub mod start_time;
 pub mod state;
+pub mod tcp_conn;
 pub mod tcp_port;
 pub mod threads;
 pub mod tree;
@@ -56,7 +60,9 @@ pub mod uid_fs;
 pub mod uid_login;
 pub mod uid_real;
 pub mod uid_saved;
+pub mod unix_socket;
 pub mod usage_cpu;
+pub mod usage_fd;
 pub mod usage_mem;
 pub mod user;
 pub mod user_fs;
@@ -75,16 +81,16 @@ pub mod vm_rss;
 pub mod vm_size;
 pub mod vm_stack;
 pub mod vm_swap;
+pub mod voluntary_context_sw;
 pub mod wchan;
 pub mod work_dir;
 pub mod write_bytes;
 
+pub use self::cap::Cap;
 pub use self::ccgroup::Ccgroup;
 pub use self::cgroup::Cgroup;
 pub use self::command::Command;
 pub use self::context_sw::ContextSw;
-pub use self::voluntary_context_sw::VoluntaryContextSw;
-pub use self::involuntary_context_sw::InvoluntaryContextSw;
 pub use self::cpu_time::CpuTime;
 #[cfg(feature = "docker")]
 pub use self::docker::Docker;
@@ -93,6 +99,9 @@ pub use self::elapsed_time::ElapsedTime;
 pub use self::empty::Empty;
 pub use self::env::Env;
 pub use self::esp::Esp;
+pub use self::fd_count::FdCount;
+pub use self::fd_limit_hard::FdLimitHard;
+pub use self::fd_limit_soft::FdLimitSoft;
 pub use self::file_name::FileName;
 pub use self::gid::Gid;
 pub use self::gid_fs::GidFs;
@@ -102,10 +111,14 @@ pub use self::group::Group;
 pub use self::group_fs::GroupFs;
 pub use self::group_real::GroupReal;
 pub use self::group_saved::GroupSaved;
+pub use self::inner_pid::InnerPid;
+pub use self::involuntary_context_sw::InvoluntaryContextSw;
 pub use self::maj_flt::MajFlt;
 pub use self::min_flt::MinFlt;
 pub use self::multi_slot::MultiSlot;
 pub use self::nice::Nice;
+pub use self::ns::Ns;
+pub use self::ns_non_host::NsNonHost;
 pub use self::pgid::Pgid;
 pub use self::pid::Pid;
 pub use self::policy::Policy;
@@ -117,15 +130,13 @@ pub use self::rt_priority::RtPriority;
 pub use self::sec_context::SecContext;
 pub use self::separator::Separator;
 pub use self::session::Session;
-pub use self::shd_pnd::ShdPnd;
-pub use self::sig_blk::SigBlk;
-pub use self::sig_cgt::SigCgt;
-pub use self::sig_ign::SigIgn;
-pub use self::sig_pnd::SigPnd;
+pub use self::sig::{Sig, SigSet};
 pub use self::slot::Slot;
+pub use self::smaps::{Smaps, SmapsKind};
 pub use self::ssb::Ssb;
 pub use self::start_time::StartTime;
 pub use self::state::State;
+pub use self::tcp_conn::TcpConn;
 pub use self::tcp_port::TcpPort;
 pub use self::threads::Threads;
 pub use self::tree::Tree;
@@ -137,7 +148,9 @@ pub use self::uid_fs::UidFs;
 pub use self::uid_login::UidLogin;
 pub use self::uid_real::UidReal;
 pub use self::uid_saved::UidSaved;
+pub use self::unix_socket::UnixSocket;
 pub use self::usage_cpu::UsageCpu;
+pub use self::usage_fd::UsageFd;
 pub use self::usage_mem::UsageMem;
 pub use self::user::User;
 pub use self::user_fs::UserFs;
@@ -156,11 +169,14 @@ pub use self::vm_rss::VmRss;
 pub use self::vm_size::VmSize;
 pub use self::vm_stack::VmStack;
 pub use self::vm_swap::VmSwap;
+pub use self::voluntary_context_sw::VoluntaryContextSw;
 pub use self::wchan::Wchan;
 pub use self::work_dir::WorkDir;
 pub use self::write_bytes::WriteBytes;
 
+use crate::capability::CapSet;
 use crate::column::Column;
+use crate::util::SystemState;
 use once_cell::sync::Lazy;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -219,6 +235,8 @@ pub enum ConfigColumnKind {
     StartTime,
     State,
     TcpPort,
+    // After TcpPort so that `tcp` is still resolved to TcpPort
+    TcpConn,
     Threads,
     Tree,
     TreeSlot,
@@ -229,7 +247,9 @@ pub enum ConfigColumnKind {
     UidLogin,
     UidReal,
     UidSaved,
+    UnixSocket,
     UsageCpu,
+    UsageFd,
     UsageMem,
     User,
     UserFs,
@@ -249,15 +269,40 @@ pub enum ConfigColumnKind {
     VmSize,
     VmStack,
     VmSwap,
+    // After VmSwap so that `swap` is still resolved to VmSwap
+    SwapPss,
     Wchan,
     WorkDir,
     WriteBytes,
+    // At the end so that existing abbreviations like `pp` and `pi` are still resolved as before
+    CapAmb,
+    CapBnd,
+    CapEff,
+    CapPrm,
+    FdCount,
+    FdLimitHard,
+    FdLimitSoft,
+    Pss,
+    Uss,
+    SharedClean,
+    SharedDirty,
+    NsCgroup,
+    NsIpc,
+    NsMnt,
+    NsNet,
+    NsNonHost,
+    NsPid,
+    NsTime,
+    NsUser,
+    NsUts,
+    InnerPid,
 }
 
 // ---------------------------------------------------------------------------------------------------------------------
 // gen_column
 // ---------------------------------------------------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 pub fn gen_column(
     kind: &ConfigColumnKind,
     header: Option<String>,
@@ -266,24 +311,32 @@ pub fn gen_column(
     abbr_sid: bool,
     tree_symbols: &[String; 5],
     procfs: Option<PathBuf>,
+    system_state: SystemState,
 ) -> Box<dyn Column> {
     match kind {
+        ConfigColumnKind::CapAmb => Box::new(Cap::new(header, procfs, CapSet::Amb)),
+        ConfigColumnKind::CapBnd => Box::new(Cap::new(header, procfs, CapSet::Bnd)),
+        ConfigColumnKind::CapEff => Box::new(Cap::new(header, procfs, CapSet::Eff)),
+        ConfigColumnKind::CapPrm => Box::new(Cap::new(header, procfs, CapSet::Prm)),
         ConfigColumnKind::Ccgroup => Box::new(Ccgroup::new(header)),
         ConfigColumnKind::Cgroup => Box::new(Cgroup::new(header)),
         ConfigColumnKind::Command => Box::new(Command::new(header)),
         ConfigColumnKind::ContextSw => Box::new(ContextSw::new(header)),
         ConfigColumnKind::VoluntaryContextSw => Box::new(VoluntaryContextSw::new(header)),
         ConfigColumnKind::InvoluntaryContextSw => Box::new(InvoluntaryContextSw::new(header)),
-        ConfigColumnKind::CpuTime => Box::new(CpuTime::new(header)),
+        ConfigColumnKind::CpuTime => Box::new(CpuTime::new(header, system_state)),
         #[cfg(feature = "docker")]
         ConfigColumnKind::Docker => Box::new(Docker::new(header, _docker_path)),
         #[cfg(not(feature = "docker"))]
         ConfigColumnKind::Docker => Box::new(Empty::new()),
         ConfigColumnKind::Eip => Box::new(Eip::new(header)),
-        ConfigColumnKind::ElapsedTime => Box::new(ElapsedTime::new(header)),
+        ConfigColumnKind::ElapsedTime => Box::new(ElapsedTime::new(header, system_state)),
         ConfigColumnKind::Empty => Box::new(Empty::new()),
         ConfigColumnKind::Env => Box::new(Env::new(header, procfs)),
         ConfigColumnKind::Esp => Box::new(Esp::new(header)),
+        ConfigColumnKind::FdCount => Box::new(FdCount::new(header)),
+        ConfigColumnKind::FdLimitHard => Box::new(FdLimitHard::new(header)),
+        ConfigColumnKind::FdLimitSoft => Box::new(FdLimitSoft::new(header)),
         ConfigColumnKind::FileName => Box::new(FileName::new(header)),
         ConfigColumnKind::Gid => Box::new(Gid::new(header, abbr_sid)),
         ConfigColumnKind::GidFs => Box::new(GidFs::new(header)),
@@ -293,43 +346,61 @@ pub fn gen_column(
         ConfigColumnKind::GroupFs => Box::new(GroupFs::new(header)),
         ConfigColumnKind::GroupReal => Box::new(GroupReal::new(header)),
         ConfigColumnKind::GroupSaved => Box::new(GroupSaved::new(header)),
+        ConfigColumnKind::InnerPid => Box::new(InnerPid::new(header)),
         ConfigColumnKind::MajFlt => Box::new(MajFlt::new(header)),
         ConfigColumnKind::MinFlt => Box::new(MinFlt::new(header)),
         ConfigColumnKind::MultiSlot => Box::new(MultiSlot::new()),
         ConfigColumnKind::Nice => Box::new(Nice::new(header)),
+        ConfigColumnKind::NsCgroup => Box::new(Ns::new(header, procfs, "cgroup")),
+        ConfigColumnKind::NsIpc => Box::new(Ns::new(header, procfs, "ipc")),
+        ConfigColumnKind::NsMnt => Box::new(Ns::new(header, procfs, "mnt")),
+        ConfigColumnKind::NsNet => Box::new(Ns::new(header, procfs, "net")),
+        ConfigColumnKind::NsNonHost => Box::new(NsNonHost::new(header, procfs)),
+        ConfigColumnKind::NsPid => Box::new(Ns::new(header, procfs, "pid")),
+        ConfigColumnKind::NsTime => Box::new(Ns::new(header, procfs, "time")),
+        ConfigColumnKind::NsUser => Box::new(Ns::new(header, procfs, "user")),
+        ConfigColumnKind::NsUts => Box::new(Ns::new(header, procfs, "uts")),
         ConfigColumnKind::Pgid => Box::new(Pgid::new(header)),
         ConfigColumnKind::Pid => Box::new(Pid::new(header)),
         ConfigColumnKind::Policy => Box::new(Policy::new(header)),
         ConfigColumnKind::Ppid => Box::new(Ppid::new(header)),
         ConfigColumnKind::Priority => Box::new(Priority::new(header)),
         ConfigColumnKind::Processor => Box::new(Processor::new(header)),
+        ConfigColumnKind::Pss => Box::new(Smaps::new(header, SmapsKind::Pss)),
         ConfigColumnKind::ReadBytes => Box::new(ReadBytes::new(header)),
         ConfigColumnKind::RtPriority => Box::new(RtPriority::new(header)),
         ConfigColumnKind::SecContext => Box::new(SecContext::new(header, procfs)),
         ConfigColumnKind::Separator => Box::new(Separator::new(separator)),
         ConfigColumnKind::Session => Box::new(Session::new(header)),
-        ConfigColumnKind::ShdPnd => Box::new(ShdPnd::new(header)),
+        ConfigColumnKind::SharedClean => Box::new(Smaps::new(header, SmapsKind::SharedClean)),
+        ConfigColumnKind::SharedDirty => Box::new(Smaps::new(header, SmapsKind::SharedDirty)),
+        ConfigColumnKind::ShdPnd => Box::new(Sig::new(header, SigSet::ShdPnd)),
         ConfigColumnKind::Slot => Box::new(Slot::new()),
-        ConfigColumnKind::SigBlk => Box::new(SigBlk::new(header)),
-        ConfigColumnKind::SigCgt => Box::new(SigCgt::new(header)),
-        ConfigColumnKind::SigIgn => Box::new(SigIgn::new(header)),
-        ConfigColumnKind::SigPnd => Box::new(SigPnd::new(header)),
+        ConfigColumnKind::SigBlk => Box::new(Sig::new(header, SigSet::Blk)),
+        ConfigColumnKind::SigCgt => Box::new(Sig::new(header, SigSet::Cgt)),
+        ConfigColumnKind::SigIgn => Box::new(Sig::new(header, SigSet::Ign)),
+        ConfigColumnKind::SigPnd => Box::new(Sig::new(header, SigSet::Pnd)),
         ConfigColumnKind::Ssb => Box::new(Ssb::new(header)),
-        ConfigColumnKind::StartTime => Box::new(StartTime::new(header)),
+        ConfigColumnKind::StartTime => Box::new(StartTime::new(header, system_state)),
         ConfigColumnKind::State => Box::new(State::new(header)),
-        ConfigColumnKind::TcpPort => Box::new(TcpPort::new(header)),
+        ConfigColumnKind::SwapPss => Box::new(Smaps::new(header, SmapsKind::SwapPss)),
+        ConfigColumnKind::TcpConn => Box::new(TcpConn::new(header, procfs)),
+        ConfigColumnKind::TcpPort => Box::new(TcpPort::new(header, procfs)),
         ConfigColumnKind::Threads => Box::new(Threads::new(header)),
         ConfigColumnKind::Tree => Box::new(Tree::new(tree_symbols)),
         ConfigColumnKind::TreeSlot => Box::new(TreeSlot::new()),
         ConfigColumnKind::Tty => Box::new(Tty::new(header)),
-        ConfigColumnKind::UdpPort => Box::new(UdpPort::new(header)),
+        ConfigColumnKind::UdpPort => Box::new(UdpPort::new(header, procfs)),
         ConfigColumnKind::Uid => Box::new(Uid::new(header, abbr_sid)),
         ConfigColumnKind::UidFs => Box::new(UidFs::new(header)),
         ConfigColumnKind::UidLogin => Box::new(UidLogin::new(header)),
         ConfigColumnKind::UidReal => Box::new(UidReal::new(header)),
         ConfigColumnKind::UidSaved => Box::new(UidSaved::new(header)),
-        ConfigColumnKind::UsageCpu => Box::new(UsageCpu::new(header)),
-        ConfigColumnKind::UsageMem => Box::new(UsageMem::new(header)),
+        ConfigColumnKind::UnixSocket => Box::new(UnixSocket::new(header, procfs)),
+        ConfigColumnKind::UsageCpu => Box::new(UsageCpu::new(header, system_state)),
+        ConfigColumnKind::UsageFd => Box::new(UsageFd::new(header)),
+        ConfigColumnKind::UsageMem => Box::new(UsageMem::new(header, system_state)),
+        ConfigColumnKind::Uss => Box::new(Smaps::new(header, SmapsKind::Uss)),
         ConfigColumnKind::User => Box::new(User::new(header, abbr_sid)),
         ConfigColumnKind::UserFs => Box::new(UserFs::new(header)),
         ConfigColumnKind::UserLogin => Box::new(UserLogin::new(header)),
@@ -344,7 +415,7 @@ pub fn gen_column(
         ConfigColumnKind::VmPeak => Box::new(VmPeak::new(header)),
         ConfigColumnKind::VmPin => Box::new(VmPin::new(header)),
         ConfigColumnKind::VmPte => Box::new(VmPte::new(header)),
-        ConfigColumnKind::VmRss => Box::new(VmRss::new(header)),
+        ConfigColumnKind::VmRss => Box::new(VmRss::new(header, system_state)),
         ConfigColumnKind::VmSize => Box::new(VmSize::new(header)),
         ConfigColumnKind::VmStack => Box::new(VmStack::new(header)),
         ConfigColumnKind::VmSwap => Box::new(VmSwap::new(header)),
@@ -361,6 +432,19 @@ pub fn gen_column(
 pub static KIND_LIST: Lazy<BTreeMap<ConfigColumnKind, (&'static str, &'static str)>> =
     Lazy::new(|| {
         [
+            (ConfigColumnKind::CapAmb, ("CapAmb", "Ambient capabilities")),
+            (
+                ConfigColumnKind::CapBnd,
+                ("CapBnd", "Bounding capabilities"),
+            ),
+            (
+                ConfigColumnKind::CapEff,
+                ("CapEff", "Effective capabilities"),
+            ),
+            (
+                ConfigColumnKind::CapPrm,
+                ("CapPrm", "Permitted capabilities"),
+            ),
             (
                 ConfigColumnKind::Ccgroup,
                 ("Ccgroup", "Control group by compressed format"),
@@ -398,6 +482,18 @@ pub static KIND_LIST: Lazy<BTreeMap<ConfigColumnKind, (&'static str, &'static st
             (ConfigColumnKind::Empty, ("Empty", "Empty")),
             (ConfigColumnKind::Env, ("Env", "Environment variables")),
             (ConfigColumnKind::Esp, ("Esp", "Stack pointer")),
+            (
+                ConfigColumnKind::FdCount,
+                ("FdCount", "Open file descriptor count"),
+            ),
+            (
+                ConfigColumnKind::FdLimitHard,
+                ("FdLimitHard", "Hard limit of open file descriptors"),
+            ),
+            (
+                ConfigColumnKind::FdLimitSoft,
+                ("FdLimitSoft", "Soft limit of open file descriptors"),
+            ),
             (ConfigColumnKind::FileName, ("FileName", "File name")),
             (ConfigColumnKind::Gid, ("Gid", "Group ID")),
             (ConfigColumnKind::GidFs, ("GidFs", "File system group ID")),
@@ -416,6 +512,10 @@ pub static KIND_LIST: Lazy<BTreeMap<ConfigColumnKind, (&'static str, &'static st
                 ConfigColumnKind::GroupSaved,
                 ("GroupSaved", "Saved group name"),
             ),
+            (
+                ConfigColumnKind::InnerPid,
+                ("InnerPid", "Process ID in the innermost PID namespace"),
+            ),
             (
                 ConfigColumnKind::MajFlt,
                 ("MajFlt", "Major page fault count"),
@@ -429,6 +529,18 @@ pub static KIND_LIST: Lazy<BTreeMap<ConfigColumnKind, (&'static str, &'static st
                 ("MultiSlot", "Slot for `--insert` option"),
             ),
             (ConfigColumnKind::Nice, ("Nice", "Nice value")),
+            (ConfigColumnKind::NsCgroup, ("NsCgroup", "Cgroup namespace")),
+            (ConfigColumnKind::NsIpc, ("NsIpc", "IPC namespace")),
+            (ConfigColumnKind::NsMnt, ("NsMnt", "Mount namespace")),
+            (ConfigColumnKind::NsNet, ("NsNet", "Network namespace")),
+            (
+                ConfigColumnKind::NsNonHost,
+                ("NsNonHost", "Namespaces different from PID 1"),
+            ),
+            (ConfigColumnKind::NsPid, ("NsPid", "PID namespace")),
+            (ConfigColumnKind::NsTime, ("NsTime", "Time namespace")),
+            (ConfigColumnKind::NsUser, ("NsUser", "User namespace")),
+            (ConfigColumnKind::NsUts, ("NsUts", "UTS namespace")),
             (ConfigColumnKind::Pgid, ("Pgid", "Process group ID")),
             (ConfigColumnKind::Pid, ("Pid", "Process ID")),
             (ConfigColumnKind::Policy, ("Policy", "Scheduling policy")),
@@ -438,6 +550,7 @@ pub static KIND_LIST: Lazy<BTreeMap<ConfigColumnKind, (&'static str, &'static st
                 ConfigColumnKind::Processor,
                 ("Processor", "Currently assigned processor"),
             ),
+            (ConfigColumnKind::Pss, ("Pss", "Proportional set size")),
             (
                 ConfigColumnKind::ReadBytes,
                 ("ReadBytes", "Read bytes from storage"),
@@ -455,6 +568,14 @@ pub static KIND_LIST: Lazy<BTreeMap<ConfigColumnKind, (&'static str, &'static st
                 ("Separator", "Show | for column separation"),
             ),
             (ConfigColumnKind::Session, ("Session", "Process Session ID")),
+            (
+                ConfigColumnKind::SharedClean,
+                ("SharedClean", "Shared clean pages size"),
+            ),
+            (
+                ConfigColumnKind::SharedDirty,
+                ("SharedDirty", "Shared dirty pages size"),
+            ),
             (
                 ConfigColumnKind::ShdPnd,
                 ("ShdPnd", "Pending signal mask for process"),
@@ -476,6 +597,14 @@ pub static KIND_LIST: Lazy<BTreeMap<ConfigColumnKind, (&'static str, &'static st
             ),
             (ConfigColumnKind::StartTime, ("StartTime", "Starting time")),
             (ConfigColumnKind::State, ("State", "Process state")),
+            (
+                ConfigColumnKind::SwapPss,
+                ("SwapPss", "Proportional swap size"),
+            ),
+            (
+                ConfigColumnKind::TcpConn,
+                ("TcpConn", "Established TCP connections"),
+            ),
             (ConfigColumnKind::TcpPort, ("TcpPort", "Bound TCP ports")),
             (ConfigColumnKind::Threads, ("Threads", "Thread count")),
             (
@@ -489,11 +618,20 @@ pub static KIND_LIST: Lazy<BTreeMap<ConfigColumnKind, (&'static str, &'static st
             (ConfigColumnKind::UidLogin, ("UidLogin", "Login user ID")),
             (ConfigColumnKind::UidReal, ("UidReal", "Real user ID")),
             (ConfigColumnKind::UidSaved, ("UidSaved", "Saved user ID")),
+            (
+                ConfigColumnKind::UnixSocket,
+                ("UnixSocket", "Listening Unix domain sockets"),
+            ),
             (ConfigColumnKind::UsageCpu, ("UsageCpu", "CPU utilization")),
+            (
+                ConfigColumnKind::UsageFd,
+                ("UsageFd", "File descriptor utilization"),
+            ),
             (
                 ConfigColumnKind::UsageMem,
                 ("UsageMem", "Memory utilization"),
             ),
+            (ConfigColumnKind::Uss, ("Uss", "Unique set size")),
             (ConfigColumnKind::User, ("User", "User name")),
             (
                 ConfigColumnKind::UserFs,
@@ -749,6 +887,20 @@ align = "Left"
 #[cfg(test)]
 pub static CONFIG_ALL: &str = r#"
 [[columns]]
+kind = "CapAmb"
+style = "White"
+[[columns]]
+kind = "CapBnd"
+style = "White"
+mode = "Compact"
+[[columns]]
+kind = "CapEff"
+style = "White"
+[[columns]]
+kind = "CapPrm"
+style = "White"
+mode = "Hex"
+[[columns]]
 kind = "Ccgroup"
 style = "BrightRed"
 align = "Left"
@@ -795,6 +947,15 @@ style = "BrightYellow"
 kind = "Esp"
 style = "BrightBlue"
 [[columns]]
+kind = "FdCount"
+style = "White"
+[[columns]]
+kind = "FdLimitHard"
+style = "White"
+[[columns]]
+kind = "FdLimitSoft"
+style = "White"
+[[columns]]
 kind = "FileName"
 style = "BrightBlue"
 [[columns]]
@@ -822,6 +983,10 @@ style = "White"
 kind = "GroupSaved"
 style = "White"
 [[columns]]
+kind = "InnerPid"
+style = "BrightYellow|Yellow"
+numeric_search = true
+[[columns]]
 kind = "MajFlt"
 style = "BrightCyan"
 [[columns]]
@@ -834,6 +999,33 @@ style = "BrightWhite"
 kind = "Nice"
 style = "Red"
 [[columns]]
+kind = "NsCgroup"
+style = "White"
+[[columns]]
+kind = "NsIpc"
+style = "White"
+[[columns]]
+kind = "NsMnt"
+style = "White"
+[[columns]]
+kind = "NsNet"
+style = "White"
+[[columns]]
+kind = "NsNonHost"
+style = "White"
+[[columns]]
+kind = "NsPid"
+style = "White"
+[[columns]]
+kind = "NsTime"
+style = "White"
+[[columns]]
+kind = "NsUser"
+style = "White"
+[[columns]]
+kind = "NsUts"
+style = "White"
+[[columns]]
 kind = "Pgid"
 style = "Yellow"
 [[columns]]
@@ -852,6 +1044,9 @@ style = "Blue"
 kind = "Processor"
 style = "Magenta"
 [[columns]]
+kind = "Pss"
+style = "ByUnit"
+[[columns]]
 kind = "ReadBytes"
 style = "Cyan"
 [[columns]]
@@ -867,6 +1062,12 @@ style = "White"
 kind = "Session"
 style = "Yellow"
 [[columns]]
+kind = "SharedClean"
+style = "ByUnit"
+[[columns]]
+kind = "SharedDirty"
+style = "ByUnit"
+[[columns]]
 kind = "ShdPnd"
 style = "White"
 [[columns]]
@@ -879,6 +1080,10 @@ style = "White"
 kind = "SigIgn"
 style = "White"
 [[columns]]
+kind = "SigIgn"
+style = "White"
+mode = "Name"
+[[columns]]
 kind = "SigPnd"
 style = "White"
 [[columns]]
@@ -891,6 +1096,20 @@ style = "White"
 kind = "State"
 style = "White"
 [[columns]]
+kind = "SwapPss"
+style = "ByUnit"
+[[columns]]
+kind = "TcpConn"
+style = "White"
+[[columns]]
+kind = "TcpConn"
+style = "White"
+mode = "List"
+[[columns]]
+kind = "TcpPort"
+style = "White"
+mode = "Address"
+[[columns]]
 kind = "TcpPort"
 style = "White"
 [[columns]]
@@ -905,6 +1124,10 @@ style = "White"
 [[columns]]
 kind = "UdpPort"
 style = "White"
+mode = "Address"
+[[columns]]
+kind = "UdpPort"
+style = "White"
 [[columns]]
 kind = "Uid"
 style = "White"
@@ -921,12 +1144,21 @@ style = "White"
 kind = "UidSaved"
 style = "White"
 [[columns]]
+kind = "UnixSocket"
+style = "White"
+[[columns]]
 kind = "UsageCpu"
 style = "White"
 [[columns]]
+kind = "UsageFd"
+style = "ByPercentage"
+[[columns]]
 kind = "UsageMem"
 style = "White"
 [[columns]]
+kind = "Uss"
+style = "ByUnit"
+[[columns]]
 kind = "User"
 style = "White"
 [[columns]]
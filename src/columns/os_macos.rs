@@ -95,6 +95,7 @@ pub use self::vm_total::VmTotal;
 pub use self::write_bytes::WriteBytes;
 
 use crate::column::Column;
+use crate::util::SystemState;
 use once_cell::sync::Lazy;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -159,6 +160,7 @@ pub enum ConfigColumnKind {
 // gen_column
 // ---------------------------------------------------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 pub fn gen_column(
     kind: &ConfigColumnKind,
     header: Option<String>,
@@ -166,18 +168,19 @@ pub fn gen_column(
     separator: &str,
     abbr_sid: bool,
     tree_symbols: &[String; 5],
-    _procfs: Option<PathBuf>,
+    procfs: Option<PathBuf>,
+    system_state: SystemState,
 ) -> Box<dyn Column> {
     match kind {
         ConfigColumnKind::Arch => Box::new(Arch::new(header)),
         ConfigColumnKind::Command => Box::new(Command::new(header)),
         ConfigColumnKind::ContextSw => Box::new(ContextSw::new(header)),
-        ConfigColumnKind::CpuTime => Box::new(CpuTime::new(header)),
+        ConfigColumnKind::CpuTime => Box::new(CpuTime::new(header, system_state)),
         #[cfg(feature = "docker")]
         ConfigColumnKind::Docker => Box::new(Docker::new(header, _docker_path)),
         #[cfg(not(feature = "docker"))]
         ConfigColumnKind::Docker => Box::new(Empty::new()),
-        ConfigColumnKind::ElapsedTime => Box::new(ElapsedTime::new(header)),
+        ConfigColumnKind::ElapsedTime => Box::new(ElapsedTime::new(header, system_state)),
         ConfigColumnKind::Empty => Box::new(Empty::new()),
         ConfigColumnKind::Gid => Box::new(Gid::new(header, abbr_sid)),
         ConfigColumnKind::GidReal => Box::new(GidReal::new(header)),
@@ -198,26 +201,26 @@ pub fn gen_column(
         ConfigColumnKind::Separator => Box::new(Separator::new(separator)),
         ConfigColumnKind::Session => Box::new(Session::new(header)),
         ConfigColumnKind::Slot => Box::new(Slot::new()),
-        ConfigColumnKind::StartTime => Box::new(StartTime::new(header)),
+        ConfigColumnKind::StartTime => Box::new(StartTime::new(header, system_state)),
         ConfigColumnKind::State => Box::new(State::new(header)),
-        ConfigColumnKind::TcpPort => Box::new(TcpPort::new(header)),
+        ConfigColumnKind::TcpPort => Box::new(TcpPort::new(header, procfs)),
         ConfigColumnKind::Threads => Box::new(Threads::new(header)),
         ConfigColumnKind::Tree => Box::new(Tree::new(tree_symbols)),
         ConfigColumnKind::TreeSlot => Box::new(TreeSlot::new()),
         ConfigColumnKind::Tty => Box::new(Tty::new(header)),
-        ConfigColumnKind::UdpPort => Box::new(UdpPort::new(header)),
+        ConfigColumnKind::UdpPort => Box::new(UdpPort::new(header, procfs)),
         ConfigColumnKind::Uid => Box::new(Uid::new(header, abbr_sid)),
         ConfigColumnKind::UidReal => Box::new(UidReal::new(header)),
         ConfigColumnKind::UidSaved => Box::new(UidSaved::new(header)),
-        ConfigColumnKind::UsageCpu => Box::new(UsageCpu::new(header)),
-        ConfigColumnKind::UsageMem => Box::new(UsageMem::new(header)),
+        ConfigColumnKind::UsageCpu => Box::new(UsageCpu::new(header, system_state)),
+        ConfigColumnKind::UsageMem => Box::new(UsageMem::new(header, system_state)),
         ConfigColumnKind::User => Box::new(User::new(header, abbr_sid)),
         ConfigColumnKind::UserReal => Box::new(UserReal::new(header)),
         ConfigColumnKind::UserSaved => Box::new(UserSaved::new(header)),
         ConfigColumnKind::Username => Box::new(User::new(header, abbr_sid)),
-        ConfigColumnKind::VmRss => Box::new(VmRss::new(header)),
+        ConfigColumnKind::VmRss => Box::new(VmRss::new(header, system_state)),
         ConfigColumnKind::VmSize => Box::new(VmSize::new(header)),
-        ConfigColumnKind::VmTotal => Box::new(VmTotal::new(header)),
+        ConfigColumnKind::VmTotal => Box::new(VmTotal::new(header, system_state)),
         ConfigColumnKind::WriteBytes => Box::new(WriteBytes::new(header)),
     }
 }
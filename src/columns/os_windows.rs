@@ -61,6 +61,7 @@ pub use self::vm_swap::VmSwap;
 pub use self::write_bytes::WriteBytes;
 
 use crate::column::Column;
+use crate::util::SystemState;
 use once_cell::sync::Lazy;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -108,6 +109,7 @@ pub enum ConfigColumnKind {
 // gen_column
 // ---------------------------------------------------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 pub fn gen_column(
     kind: &ConfigColumnKind,
     header: Option<String>,
@@ -115,12 +117,13 @@ pub fn gen_column(
     separator: &str,
     abbr_sid: bool,
     tree_symbols: &[String; 5],
-    _procfs: Option<PathBuf>,
+    procfs: Option<PathBuf>,
+    system_state: SystemState,
 ) -> Box<dyn Column> {
     match kind {
         ConfigColumnKind::Command => Box::new(Command::new(header)),
-        ConfigColumnKind::CpuTime => Box::new(CpuTime::new(header)),
-        ConfigColumnKind::ElapsedTime => Box::new(ElapsedTime::new(header)),
+        ConfigColumnKind::CpuTime => Box::new(CpuTime::new(header, system_state)),
+        ConfigColumnKind::ElapsedTime => Box::new(ElapsedTime::new(header, system_state)),
         ConfigColumnKind::Empty => Box::new(Empty::new()),
         ConfigColumnKind::Gid => Box::new(Gid::new(header, abbr_sid)),
         ConfigColumnKind::Group => Box::new(Group::new(header, abbr_sid)),
@@ -132,19 +135,19 @@ pub fn gen_column(
         ConfigColumnKind::ReadBytes => Box::new(ReadBytes::new(header)),
         ConfigColumnKind::Separator => Box::new(Separator::new(separator)),
         ConfigColumnKind::Slot => Box::new(Slot::new()),
-        ConfigColumnKind::StartTime => Box::new(StartTime::new(header)),
-        ConfigColumnKind::TcpPort => Box::new(TcpPort::new(header)),
+        ConfigColumnKind::StartTime => Box::new(StartTime::new(header, system_state)),
+        ConfigColumnKind::TcpPort => Box::new(TcpPort::new(header, procfs)),
         ConfigColumnKind::Threads => Box::new(Threads::new(header)),
         ConfigColumnKind::Tree => Box::new(Tree::new(tree_symbols)),
         ConfigColumnKind::TreeSlot => Box::new(TreeSlot::new()),
         ConfigColumnKind::Uid => Box::new(Uid::new(header, abbr_sid)),
-        ConfigColumnKind::UsageCpu => Box::new(UsageCpu::new(header)),
-        ConfigColumnKind::UsageMem => Box::new(UsageMem::new(header)),
+        ConfigColumnKind::UsageCpu => Box::new(UsageCpu::new(header, system_state)),
+        ConfigColumnKind::UsageMem => Box::new(UsageMem::new(header, system_state)),
         ConfigColumnKind::User => Box::new(User::new(header, abbr_sid)),
         ConfigColumnKind::VmHwm => Box::new(VmHwm::new(header)),
         ConfigColumnKind::VmPeak => Box::new(VmPeak::new(header)),
         ConfigColumnKind::VmPin => Box::new(VmPin::new(header)),
-        ConfigColumnKind::VmRss => Box::new(VmRss::new(header)),
+        ConfigColumnKind::VmRss => Box::new(VmRss::new(header, system_state)),
         ConfigColumnKind::VmSize => Box::new(VmSize::new(header)),
         ConfigColumnKind::VmSwap => Box::new(VmSwap::new(header)),
         ConfigColumnKind::WriteBytes => Box::new(WriteBytes::new(header)),
@@ -1,5 +1,5 @@
 use crate::process::ProcessInfo;
-use crate::{column_default, Column};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -29,9 +29,10 @@ impl Pgid {
 impl Column for Pgid {
     fn add(&mut self, proc: &ProcessInfo) {
         let raw_content = proc.curr_proc.stat().pgrp;
-        let fmt_content = match proc.curr_proc {
-            crate::process::ProcessTask::Process { .. } => format!("{raw_content}"),
-            _ => format!("[{raw_content}]"),
+        let fmt_content = if proc.curr_proc.is_task() {
+            format!("[{raw_content}]")
+        } else {
+            format!("{raw_content}")
         };
 
         self.fmt_contents.insert(proc.pid, fmt_content);
@@ -1,5 +1,5 @@
 use crate::process::ProcessInfo;
-use crate::{column_default, Column};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -33,7 +33,9 @@ impl SecContext {
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl Column for SecContext {
     fn add(&mut self, proc: &ProcessInfo) {
-        let fmt_content = if let Ok(proc) = crate::util::process_new(proc.pid, &self.procfs) {
+        let fmt_content = if !proc.curr_proc.is_record()
+            && let Ok(proc) = crate::util::process_new(proc.pid, &self.procfs)
+        {
             if let Ok(mut file) = proc.open_relative("attr/current") {
                 let mut ret = String::new();
                 let _ = file.read_to_string(&mut ret);
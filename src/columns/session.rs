@@ -1,5 +1,5 @@
 use crate::process::ProcessInfo;
-use crate::{column_default, Column};
+use crate::{Column, column_default};
 #[cfg(target_os = "macos")]
 use nix::unistd::{self, Pid};
 use std::cmp;
@@ -31,9 +31,10 @@ impl Session {
 impl Column for Session {
     fn add(&mut self, proc: &ProcessInfo) {
         let raw_content = proc.curr_proc.stat().session;
-        let fmt_content = match proc.curr_proc {
-            crate::process::ProcessTask::Process { .. } => format!("{raw_content}"),
-            _ => format!("[{raw_content}]"),
+        let fmt_content = if proc.curr_proc.is_task() {
+            format!("[{raw_content}]")
+        } else {
+            format!("{raw_content}")
         };
 
         self.fmt_contents.insert(proc.pid, fmt_content);
@@ -0,0 +1,214 @@
+use crate::Column;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::config::ConfigColumnMode;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::process::DataSource;
+use crate::process::ProcessInfo;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use anyhow::{Error, bail};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use procfs::process::Status;
+use std::cmp;
+use std::collections::HashMap;
+
+/// Signal mask in `/proc/<pid>/status`
+#[derive(Clone, Copy)]
+pub enum SigSet {
+    Blk,
+    Cgt,
+    Ign,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Pnd,
+    ShdPnd,
+}
+
+impl SigSet {
+    pub fn header(&self) -> &'static str {
+        match self {
+            SigSet::Blk => "SigBlk",
+            SigSet::Cgt => "SigCgt",
+            SigSet::Ign => "SigIgn",
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            SigSet::Pnd => "SigPnd",
+            SigSet::ShdPnd => "ShdPnd",
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn mask(&self, status: &Status) -> u64 {
+        match self {
+            SigSet::Blk => status.sigblk,
+            SigSet::Cgt => status.sigcgt,
+            SigSet::Ign => status.sigign,
+            SigSet::Pnd => status.sigpnd,
+            SigSet::ShdPnd => status.shdpnd,
+        }
+    }
+}
+
+pub struct Sig {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, u64>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    names: HashMap<i32, Vec<String>>,
+    width: usize,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    mode: ConfigColumnMode,
+    set: SigSet,
+}
+
+impl Sig {
+    pub fn new(header: Option<String>, set: SigSet) -> Self {
+        let header = header.unwrap_or_else(|| String::from(set.header()));
+        let unit = String::new();
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            names: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            mode: ConfigColumnMode::Hex,
+            set,
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl Column for Sig {
+    fn add(&mut self, proc: &ProcessInfo) {
+        let (fmt_content, raw_content) = if let Some(ref status) = proc.curr_status {
+            let val = self.set.mask(status);
+            let names = crate::signal::names(val);
+            let fmt_content = if self.mode == ConfigColumnMode::Name {
+                names.join(",")
+            } else {
+                format!("{val:016x}")
+            };
+            // Names are kept only if the mask is read, so that unreadable masks are null in JSON
+            self.names.insert(proc.pid, names);
+            (fmt_content, val)
+        } else {
+            (String::new(), 0)
+        };
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+    }
+
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
+    fn set_mode(&mut self, mode: &ConfigColumnMode) -> Result<(), Error> {
+        match mode {
+            ConfigColumnMode::Hex | ConfigColumnMode::Name => self.mode = mode.clone(),
+            _ => bail!("mode \"{:?}\" is not supported", mode),
+        }
+        Ok(())
+    }
+
+    // JSON output is decoded names in both modes because the raw mask is hard to use in scripts
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        self.names
+            .get(&pid)
+            .map(|x| serde_json::Value::from(x.as_slice()))
+            .unwrap_or(serde_json::Value::Null)
+    }
+
+    // Signal names like `HUP` or `SIGHUP` are searchable in both modes
+    fn find_partial(&self, pid: i32, keyword: &str, content_to_lowercase: bool) -> bool {
+        let found = if let Some(content) = self.fmt_contents.get(&pid) {
+            if content_to_lowercase {
+                content.to_ascii_lowercase().contains(keyword)
+            } else {
+                content.contains(keyword)
+            }
+        } else {
+            false
+        };
+        found || self.find_exact(pid, keyword, content_to_lowercase)
+    }
+
+    fn find_exact(&self, pid: i32, keyword: &str, content_to_lowercase: bool) -> bool {
+        let found = if let Some(content) = self.fmt_contents.get(&pid) {
+            if content_to_lowercase {
+                content.to_ascii_lowercase() == keyword
+            } else {
+                content == keyword
+            }
+        } else {
+            false
+        };
+        found
+            || self
+                .names
+                .get(&pid)
+                .map(|x| crate::signal::contains(x, keyword))
+                .unwrap_or(false)
+    }
+
+    fn is_numeric(&self) -> bool {
+        self.mode == ConfigColumnMode::Hex
+    }
+
+    crate::column_default_display_header!();
+    crate::column_default_display_unit!();
+    crate::column_default_display_content!();
+    crate::column_default_display_raw!();
+    crate::column_default_raw_value!();
+    crate::column_default_sorted_pid!(u64);
+    crate::column_default_apply_visible!();
+    crate::column_default_reset_width!();
+    crate::column_default_update_width!();
+    crate::column_default_get_width!();
+    crate::column_default_header!();
+}
+
+#[cfg(target_os = "freebsd")]
+impl Column for Sig {
+    fn add(&mut self, proc: &ProcessInfo) {
+        let info = &proc.curr_proc.info;
+        let raw_content = match self.set {
+            SigSet::Blk => info.sigmask.0[0],
+            SigSet::Cgt => info.sigcatch.0[0],
+            SigSet::Ign => info.sigignore.0[0],
+            SigSet::ShdPnd => info.siglist.0[0],
+        } as u64;
+        let fmt_content = format!("{raw_content:016x}");
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+    }
+
+    crate::column_default!(u64, true);
+}
+
+#[cfg(test)]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod tests {
+    use super::*;
+    use procfs::process::Process;
+
+    #[test]
+    fn test_sig() {
+        let mut status = Process::myself().unwrap().status().unwrap();
+        status.sigign = 1 | 1 << 14;
+        let mut column = Sig::new(None, SigSet::Ign);
+        column.add(&ProcessInfo {
+            curr_status: Some(status),
+            ..ProcessInfo::for_test(1)
+        });
+        column.add(&ProcessInfo::for_test(2));
+
+        // Names are emitted in Hex mode too
+        assert_eq!(column.fmt_contents[&1], "0000000000004001");
+        assert_eq!(column.display_json(1), serde_json::json!(["HUP", "TERM"]));
+        assert_eq!(column.display_json(2), serde_json::Value::Null);
+        assert!(column.find_exact(1, "sighup", true));
+    }
+}
@@ -1,56 +0,0 @@
-use crate::process::ProcessInfo;
-use crate::{column_default, Column};
-use std::cmp;
-use std::collections::HashMap;
-
-pub struct SigCgt {
-    header: String,
-    unit: String,
-    fmt_contents: HashMap<i32, String>,
-    raw_contents: HashMap<i32, u64>,
-    width: usize,
-}
-
-impl SigCgt {
-    pub fn new(header: Option<String>) -> Self {
-        let header = header.unwrap_or_else(|| String::from("SigCgt"));
-        let unit = String::new();
-        Self {
-            fmt_contents: HashMap::new(),
-            raw_contents: HashMap::new(),
-            width: 0,
-            header,
-            unit,
-        }
-    }
-}
-
-#[cfg(any(target_os = "linux", target_os = "android"))]
-impl Column for SigCgt {
-    fn add(&mut self, proc: &ProcessInfo) {
-        let (fmt_content, raw_content) = if let Some(ref status) = proc.curr_status {
-            let val = status.sigcgt;
-            (format!("{val:016x}"), val)
-        } else {
-            (String::new(), 0)
-        };
-
-        self.fmt_contents.insert(proc.pid, fmt_content);
-        self.raw_contents.insert(proc.pid, raw_content);
-    }
-
-    column_default!(u64, true);
-}
-
-#[cfg(target_os = "freebsd")]
-impl Column for SigCgt {
-    fn add(&mut self, proc: &ProcessInfo) {
-        let raw_content = proc.curr_proc.info.sigcatch.0[0] as u64;
-        let fmt_content = format!("{raw_content:016x}");
-
-        self.fmt_contents.insert(proc.pid, fmt_content);
-        self.raw_contents.insert(proc.pid, raw_content);
-    }
-
-    column_default!(u64, true);
-}
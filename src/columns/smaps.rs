@@ -0,0 +1,123 @@
+use crate::process::{DataSource, ProcessInfo};
+use crate::util::bytify;
+use crate::{Column, column_default};
+use std::cmp;
+use std::collections::HashMap;
+
+/// Memory size in `/proc/<pid>/smaps_rollup`
+#[derive(Clone, Copy)]
+pub enum SmapsKind {
+    Pss,
+    SharedClean,
+    SharedDirty,
+    SwapPss,
+    Uss,
+}
+
+impl SmapsKind {
+    pub fn header(&self) -> &'static str {
+        match self {
+            SmapsKind::Pss => "Pss",
+            SmapsKind::SharedClean => "SharedClean",
+            SmapsKind::SharedDirty => "SharedDirty",
+            SmapsKind::SwapPss => "SwapPss",
+            SmapsKind::Uss => "Uss",
+        }
+    }
+
+    pub fn size(&self, smaps: &HashMap<String, u64>) -> Option<u64> {
+        match self {
+            SmapsKind::Pss => smaps.get("Pss").copied(),
+            SmapsKind::SharedClean => smaps.get("Shared_Clean").copied(),
+            SmapsKind::SharedDirty => smaps.get("Shared_Dirty").copied(),
+            SmapsKind::SwapPss => smaps.get("SwapPss").copied(),
+            // Unique set size is private pages which are freed when the process exits
+            SmapsKind::Uss => smaps
+                .get("Private_Clean")
+                .zip(smaps.get("Private_Dirty"))
+                .map(|(x, y)| x + y),
+        }
+    }
+}
+
+pub struct Smaps {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, Option<u64>>,
+    width: usize,
+    kind: SmapsKind,
+}
+
+impl Smaps {
+    pub fn new(header: Option<String>, kind: SmapsKind) -> Self {
+        let header = header.unwrap_or_else(|| String::from(kind.header()));
+        let unit = String::from("[bytes]");
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+            kind,
+        }
+    }
+}
+
+impl Column for Smaps {
+    fn add(&mut self, proc: &ProcessInfo) {
+        // smaps_rollup which can't be read is null
+        let raw_content = proc.curr_smaps.as_ref().and_then(|x| self.kind.size(x));
+        let fmt_content = raw_content.map(bytify).unwrap_or_default();
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+    }
+
+    fn data_source(&self) -> DataSource {
+        DataSource::SMAPS
+    }
+
+    column_default!(Option<u64>, true);
+}
+
+#[cfg(test)]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod tests {
+    use super::*;
+
+    fn proc_with_smaps(pid: i32, smaps: Option<&[(&str, u64)]>) -> ProcessInfo {
+        ProcessInfo {
+            curr_smaps: smaps.map(|x| x.iter().map(|(k, v)| (k.to_string(), *v)).collect()),
+            ..ProcessInfo::for_test(pid)
+        }
+    }
+
+    #[test]
+    fn test_smaps() {
+        let smaps = [
+            ("Pss", 512),
+            ("Private_Clean", 1024),
+            ("Private_Dirty", 2048),
+            ("Shared_Clean", 4096),
+        ];
+        let mut uss = Smaps::new(None, SmapsKind::Uss);
+        let mut pss = Smaps::new(None, SmapsKind::Pss);
+        for c in [&mut uss, &mut pss] {
+            c.add(&proc_with_smaps(1, Some(&smaps)));
+            c.add(&proc_with_smaps(2, Some(&[("Private_Clean", 1024)])));
+            c.add(&proc_with_smaps(3, None));
+        }
+
+        assert_eq!(uss.raw_contents[&1], Some(3072));
+        assert_eq!(uss.fmt_contents[&1], bytify(3072));
+        assert_eq!(pss.raw_contents[&1], Some(512));
+
+        // Unavailable sizes are null instead of 0
+        assert_eq!(uss.raw_contents[&2], None);
+        assert_eq!(uss.fmt_contents[&2], "");
+        assert_eq!(uss.display_json(2), serde_json::Value::Null);
+        assert_eq!(pss.display_json(3), serde_json::Value::Null);
+        assert_eq!(pss.display_json(1), serde_json::Value::from(512));
+    }
+}
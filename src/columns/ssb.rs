@@ -1,5 +1,5 @@
-use crate::process::ProcessInfo;
-use crate::{column_default, Column};
+use crate::process::{DataSource, ProcessInfo};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -42,5 +42,9 @@ impl Column for Ssb {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
     column_default!(String, false);
 }
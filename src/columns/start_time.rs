@@ -1,18 +1,14 @@
 use crate::process::ProcessInfo;
-use crate::{column_default, Column};
-#[cfg(not(target_os = "windows"))]
-use chrono::offset::TimeZone;
+use crate::util::SystemState;
+use crate::{Column, column_default};
 #[cfg(any(target_os = "linux", target_os = "android"))]
 use chrono::Duration;
+#[cfg(not(target_os = "windows"))]
+use chrono::offset::TimeZone;
 use chrono::{DateTime, Local};
-#[cfg(any(target_os = "linux", target_os = "android"))]
-use once_cell::sync::Lazy;
 use std::cmp;
 use std::collections::HashMap;
 
-#[cfg(any(target_os = "linux", target_os = "android"))]
-static TICKS_PER_SECOND: Lazy<u64> = Lazy::new(procfs::ticks_per_second);
-
 pub struct StartTime {
     header: String,
     unit: String,
@@ -21,10 +17,16 @@ pub struct StartTime {
     width: usize,
     #[cfg(any(target_os = "linux", target_os = "android"))]
     boot_time: DateTime<Local>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    ticks_per_second: u64,
 }
 
 impl StartTime {
-    pub fn new(header: Option<String>) -> Self {
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "android")),
+        allow(unused_variables)
+    )]
+    pub fn new(header: Option<String>, system_state: SystemState) -> Self {
         let header = header.unwrap_or_else(|| String::from("Start"));
         let unit = String::new();
         Self {
@@ -34,7 +36,9 @@ impl StartTime {
             header,
             unit,
             #[cfg(any(target_os = "linux", target_os = "android"))]
-            boot_time: procfs::boot_time().unwrap_or_else(|_| Local.timestamp_opt(0, 0).unwrap()),
+            boot_time: Local.timestamp_opt(system_state.boot_time, 0).unwrap(),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            ticks_per_second: system_state.ticks_per_second,
         }
     }
 }
@@ -43,7 +47,7 @@ impl StartTime {
 impl Column for StartTime {
     fn add(&mut self, proc: &ProcessInfo) {
         let starttime = proc.curr_proc.stat().starttime;
-        let seconds_since_boot = starttime as f32 / *TICKS_PER_SECOND as f32;
+        let seconds_since_boot = starttime as f32 / self.ticks_per_second as f32;
         let raw_content = self.boot_time
             + Duration::try_milliseconds((seconds_since_boot * 1000.0) as i64).unwrap_or_default();
         let fmt_content = format!("{}", raw_content.format("%Y/%m/%d %H:%M"));
@@ -0,0 +1,172 @@
+use crate::Column;
+use crate::config::ConfigColumnMode;
+use crate::process::ProcessInfo;
+use crate::socket::{Socket, SocketIndex};
+use anyhow::{Error, bail};
+use procfs::net::TcpState;
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+pub struct TcpConn {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, Option<u64>>,
+    peers: HashMap<i32, Vec<String>>,
+    width: usize,
+    list: bool,
+    sockets: Rc<RefCell<SocketIndex>>,
+}
+
+impl TcpConn {
+    pub fn new(header: Option<String>, procfs: Option<PathBuf>) -> Self {
+        let header = header.unwrap_or_else(|| String::from("TCP Conn"));
+        let unit = String::new();
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            peers: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+            list: false,
+            sockets: SocketIndex::shared(&procfs),
+        }
+    }
+}
+
+impl Column for TcpConn {
+    fn add(&mut self, proc: &ProcessInfo) {
+        let mut sockets = self.sockets.borrow_mut();
+        let Some((socks, table)) = proc
+            .curr_proc
+            .sockets()
+            .ok()
+            .and_then(|socks| Some((socks, sockets.table(proc)?)))
+        else {
+            // Connections of processes whose fds can't be read are null instead of 0
+            self.fmt_contents.insert(proc.pid, String::new());
+            self.raw_contents.insert(proc.pid, None);
+            self.peers.insert(proc.pid, Vec::new());
+            return;
+        };
+
+        let mut peers = Vec::new();
+        for sock in socks {
+            if let Some(Socket::Tcp(entry)) = table.get(sock)
+                && entry.state == TcpState::Established
+            {
+                peers.push(entry.remote_address.to_string());
+            }
+        }
+        peers.sort_unstable();
+
+        let raw_content = peers.len() as u64;
+        let fmt_content = if self.list {
+            peers.join(",")
+        } else {
+            format!("{raw_content}")
+        };
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, Some(raw_content));
+        self.peers.insert(proc.pid, peers);
+    }
+
+    fn set_mode(&mut self, mode: &ConfigColumnMode) -> Result<(), Error> {
+        match mode {
+            ConfigColumnMode::Count => self.list = false,
+            ConfigColumnMode::List => self.list = true,
+            _ => bail!("mode \"{:?}\" is not supported", mode),
+        }
+        Ok(())
+    }
+
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        match self.raw_value(pid) {
+            Some(_) if self.list => self
+                .peers
+                .get(&pid)
+                .map(|x| serde_json::Value::from(x.as_slice()))
+                .unwrap_or(serde_json::Value::Null),
+            Some(x) => serde_json::Value::from(x),
+            None => serde_json::Value::Null,
+        }
+    }
+
+    // Remote addresses are searchable in both modes
+    fn find_partial(&self, pid: i32, keyword: &str, _content_to_lowercase: bool) -> bool {
+        self.peers
+            .get(&pid)
+            .map(|x| x.iter().any(|x| x.contains(keyword)))
+            .unwrap_or(false)
+    }
+
+    // Both `ip:port` and `ip` are matched
+    fn find_exact(&self, pid: i32, keyword: &str, _content_to_lowercase: bool) -> bool {
+        let keyword_ip = keyword.trim_matches(['[', ']']);
+        let matches = |peer: &String| {
+            peer == keyword
+                || peer
+                    .rsplit_once(':')
+                    .is_some_and(|(ip, _)| ip.trim_matches(['[', ']']) == keyword_ip)
+        };
+        self.peers
+            .get(&pid)
+            .map(|x| x.iter().any(matches))
+            .unwrap_or(false)
+    }
+
+    fn is_numeric(&self) -> bool {
+        !self.list
+    }
+
+    crate::column_default_display_header!();
+    crate::column_default_display_unit!();
+    crate::column_default_display_content!();
+    crate::column_default_display_raw!();
+    crate::column_default_raw_value!();
+    crate::column_default_sorted_pid!(Option<u64>);
+    crate::column_default_apply_visible!();
+    crate::column_default_reset_width!();
+    crate::column_default_update_width!();
+    crate::column_default_get_width!();
+    crate::column_default_header!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::ProcessTask;
+    use procfs::process::Process;
+
+    #[test]
+    fn test_tcp_conn() {
+        // fds of a thread are not listed
+        let unreadable = ProcessInfo {
+            curr_proc: ProcessTask::Task {
+                stat: Process::myself().unwrap().stat().unwrap(),
+                owner: 0,
+            },
+            ..ProcessInfo::for_test(2)
+        };
+
+        let mut column = TcpConn::new(None, None);
+        column.add(&ProcessInfo::for_test(1));
+        column.add(&unreadable);
+
+        assert!(column.display_json(1).is_u64());
+        assert_eq!(column.display_json(2), serde_json::Value::Null);
+        assert_eq!(column.fmt_contents[&2], "");
+        // Unreadable processes are kept in sort
+        let sorted = column.sorted_pid(&crate::config::ConfigSortOrder::Ascending);
+        assert_eq!(sorted, vec![2, 1]);
+
+        column.set_mode(&ConfigColumnMode::List).unwrap();
+        assert!(column.display_json(1).is_array());
+        assert_eq!(column.display_json(2), serde_json::Value::Null);
+    }
+}
@@ -118,9 +118,13 @@ impl Column for Tree {
         }
     }
 
-    // Tree doesn't support JSON
-    fn display_json(&self, _pid: i32) -> String {
-        "".to_string()
+    fn display_raw(&self, _pid: i32) -> Option<String> {
+        None
+    }
+
+    // Tree structure is emitted as nested "children" by View instead
+    fn display_json(&self, _pid: i32) -> serde_json::Value {
+        serde_json::Value::Null
     }
 
     fn find_partial(&self, _pid: i32, _keyword: &str, _content_to_lowercase: bool) -> bool {
@@ -131,6 +135,10 @@ impl Column for Tree {
         false
     }
 
+    fn raw_value(&self, _pid: i32) -> Option<crate::column::RawValue> {
+        None
+    }
+
     fn sorted_pid(&self, _order: &crate::config::ConfigSortOrder) -> Vec<i32> {
         let mut root_pids = Vec::new();
         for p in self.rev_tree.values() {
@@ -212,6 +220,7 @@ impl Column for Tree {
     crate::column_default_display_unit!();
     crate::column_default_get_width!();
     crate::column_default_is_numeric!(false);
+    crate::column_default_header!();
 }
 
 #[cfg(test)]
@@ -247,6 +256,8 @@ mod tests {
             curr_io: None,
             prev_io: None,
             curr_status: None,
+            curr_smaps: None,
+            curr_fd_count: None,
             interval: Duration::new(0, 0),
         };
 
@@ -265,6 +276,8 @@ mod tests {
             curr_io: None,
             prev_io: None,
             curr_status: None,
+            curr_smaps: None,
+            curr_fd_count: None,
             interval: Duration::new(0, 0),
         };
 
@@ -283,6 +296,8 @@ mod tests {
             curr_io: None,
             prev_io: None,
             curr_status: None,
+            curr_smaps: None,
+            curr_fd_count: None,
             interval: Duration::new(0, 0),
         };
 
@@ -1,11 +1,18 @@
-use crate::process::ProcessInfo;
 use crate::Column;
 #[cfg(any(target_os = "linux", target_os = "android"))]
-use procfs::net::UdpNetEntry;
+use crate::config::ConfigColumnMode;
+use crate::process::ProcessInfo;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::socket::{BoundAddr, Socket, SocketIndex};
 #[cfg(any(target_os = "linux", target_os = "android"))]
-use procfs::process::FDTarget;
+use anyhow::{Error, bail};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::cell::RefCell;
 use std::cmp;
 use std::collections::HashMap;
+use std::path::PathBuf;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::rc::Rc;
 
 pub struct UdpPort {
     header: String,
@@ -14,13 +21,19 @@ pub struct UdpPort {
     raw_contents: HashMap<i32, String>,
     width: usize,
     #[cfg(any(target_os = "linux", target_os = "android"))]
-    udp_entry: Vec<UdpNetEntry>,
+    sockets: Rc<RefCell<SocketIndex>>,
     #[cfg(any(target_os = "linux", target_os = "android"))]
-    udp6_entry: Vec<UdpNetEntry>,
+    addrs: HashMap<i32, Vec<BoundAddr>>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    address: bool,
 }
 
 impl UdpPort {
-    pub fn new(header: Option<String>) -> Self {
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "android")),
+        allow(unused_variables)
+    )]
+    pub fn new(header: Option<String>, procfs: Option<PathBuf>) -> Self {
         let header = header.unwrap_or_else(|| String::from("UDP"));
         let unit = String::new();
         Self {
@@ -30,9 +43,11 @@ impl UdpPort {
             header,
             unit,
             #[cfg(any(target_os = "linux", target_os = "android"))]
-            udp_entry: procfs::net::udp().unwrap_or_default(),
+            sockets: SocketIndex::shared(&procfs),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            addrs: HashMap::new(),
             #[cfg(any(target_os = "linux", target_os = "android"))]
-            udp6_entry: procfs::net::udp6().unwrap_or_default(),
+            address: false,
         }
     }
 }
@@ -40,26 +55,33 @@ impl UdpPort {
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl Column for UdpPort {
     fn add(&mut self, proc: &ProcessInfo) {
-        let fmt_content = if let Ok(fds) = proc.curr_proc.fd() {
-            let mut socks = Vec::new();
-            for fd in fds {
-                if let FDTarget::Socket(x) = fd.target {
-                    socks.push(x)
-                }
-            }
-
-            let mut ports = Vec::new();
+        let mut sockets = self.sockets.borrow_mut();
+        let fmt_content = if let Ok(socks) = proc.curr_proc.sockets()
+            && let Some(table) = sockets.table(proc)
+        {
+            let mut addrs = Vec::new();
             for sock in &socks {
-                let mut udp_iter = self.udp_entry.iter().chain(self.udp6_entry.iter());
-                let entry = udp_iter.find(|&x| x.inode == *sock);
-                if let Some(entry) = entry {
-                    ports.push(entry.local_address.port());
+                if let Some(Socket::Udp(entry)) = table.get(*sock) {
+                    addrs.push(BoundAddr {
+                        tcp: false,
+                        addr: entry.local_address,
+                    });
                 }
             }
-            ports.sort_unstable();
-            ports.dedup();
+            addrs.sort_unstable();
+            addrs.dedup();
 
-            format!("{ports:?}")
+            let fmt_content = if self.address {
+                let addrs: Vec<_> = addrs.iter().map(|x| x.to_string()).collect();
+                addrs.join(",")
+            } else {
+                let mut ports: Vec<_> = addrs.iter().map(|x| x.addr.port()).collect();
+                ports.sort_unstable();
+                ports.dedup();
+                format!("{ports:?}")
+            };
+            self.addrs.insert(proc.pid, addrs);
+            fmt_content
         } else {
             String::new()
         };
@@ -69,32 +91,60 @@ impl Column for UdpPort {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
-    fn find_exact(&self, pid: i32, keyword: &str, _content_to_lowercase: bool) -> bool {
-        if let Some(content) = self.fmt_contents.get(&pid) {
-            let content = content.replace(['[', ']'], "");
-            let content = content.split(',');
-            for c in content {
-                if c == keyword {
-                    return true;
-                }
-            }
+    fn set_mode(&mut self, mode: &ConfigColumnMode) -> Result<(), Error> {
+        match mode {
+            ConfigColumnMode::Port => self.address = false,
+            ConfigColumnMode::Address => self.address = true,
+            _ => bail!("mode \"{:?}\" is not supported", mode),
+        }
+        Ok(())
+    }
+
+    fn find_partial(&self, pid: i32, keyword: &str, content_to_lowercase: bool) -> bool {
+        if keyword == "public" {
+            self.find_exact(pid, keyword, content_to_lowercase)
+        } else if let Some(content) = self.fmt_contents.get(&pid) {
+            content.contains(keyword)
+        } else {
             false
+        }
+    }
+
+    fn find_exact(&self, pid: i32, keyword: &str, _content_to_lowercase: bool) -> bool {
+        if let Some(addrs) = self.addrs.get(&pid) {
+            addrs.iter().any(|x| x.matches(keyword))
         } else {
             false
         }
     }
 
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        if self.address {
+            self.addrs
+                .get(&pid)
+                .map(|x| {
+                    x.iter()
+                        .map(|x| serde_json::Value::from(x.to_string()))
+                        .collect()
+                })
+                .unwrap_or(serde_json::Value::Null)
+        } else {
+            crate::util::port_list_to_json(self.fmt_contents.get(&pid))
+        }
+    }
+
     crate::column_default_display_header!();
     crate::column_default_display_unit!();
     crate::column_default_display_content!();
-    crate::column_default_display_json!();
-    crate::column_default_find_partial!();
+    crate::column_default_display_raw!();
+    crate::column_default_raw_value!();
     crate::column_default_sorted_pid!(String);
     crate::column_default_apply_visible!();
     crate::column_default_reset_width!();
     crate::column_default_update_width!();
     crate::column_default_get_width!();
     crate::column_default_is_numeric!(false);
+    crate::column_default_header!();
 }
 
 #[cfg(target_os = "macos")]
@@ -132,15 +182,21 @@ impl Column for UdpPort {
         }
     }
 
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        crate::util::port_list_to_json(self.fmt_contents.get(&pid))
+    }
+
     crate::column_default_display_header!();
     crate::column_default_display_unit!();
     crate::column_default_display_content!();
-    crate::column_default_display_json!();
+    crate::column_default_display_raw!();
     crate::column_default_find_partial!();
+    crate::column_default_raw_value!();
     crate::column_default_sorted_pid!(String);
     crate::column_default_apply_visible!();
     crate::column_default_reset_width!();
     crate::column_default_update_width!();
     crate::column_default_get_width!();
     crate::column_default_is_numeric!(false);
+    crate::column_default_header!();
 }
@@ -0,0 +1,91 @@
+use crate::Column;
+use crate::process::ProcessInfo;
+use crate::socket::{Socket, SocketIndex};
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+pub struct UnixSocket {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, String>,
+    paths: HashMap<i32, Vec<String>>,
+    width: usize,
+    sockets: Rc<RefCell<SocketIndex>>,
+}
+
+impl UnixSocket {
+    pub fn new(header: Option<String>, procfs: Option<PathBuf>) -> Self {
+        let header = header.unwrap_or_else(|| String::from("Unix Socket"));
+        let unit = String::new();
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            paths: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+            sockets: SocketIndex::shared(&procfs),
+        }
+    }
+}
+
+impl Column for UnixSocket {
+    fn add(&mut self, proc: &ProcessInfo) {
+        let mut paths = Vec::new();
+        let mut sockets = self.sockets.borrow_mut();
+        if let Ok(socks) = proc.curr_proc.sockets()
+            && let Some(table) = sockets.table(proc)
+        {
+            for x in socks {
+                // Abstract names start with `@`.
+                if table.is_unix_listening(x)
+                    && let Some(Socket::Unix(entry)) = table.get(x)
+                    && let Some(path) = &entry.path
+                {
+                    paths.push(path.to_string_lossy().to_string());
+                }
+            }
+            paths.sort_unstable();
+            paths.dedup();
+        }
+
+        let fmt_content = paths.join(",");
+        let raw_content = fmt_content.clone();
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+        self.paths.insert(proc.pid, paths);
+    }
+
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        self.paths
+            .get(&pid)
+            .map(|x| serde_json::Value::from(x.as_slice()))
+            .unwrap_or(serde_json::Value::Null)
+    }
+
+    fn find_exact(&self, pid: i32, keyword: &str, _content_to_lowercase: bool) -> bool {
+        self.paths
+            .get(&pid)
+            .map(|x| x.iter().any(|x| x == keyword))
+            .unwrap_or(false)
+    }
+
+    crate::column_default_display_header!();
+    crate::column_default_display_unit!();
+    crate::column_default_display_content!();
+    crate::column_default_display_raw!();
+    crate::column_default_find_partial!();
+    crate::column_default_raw_value!();
+    crate::column_default_sorted_pid!(String);
+    crate::column_default_apply_visible!();
+    crate::column_default_reset_width!();
+    crate::column_default_update_width!();
+    crate::column_default_get_width!();
+    crate::column_default_is_numeric!(false);
+    crate::column_default_header!();
+}
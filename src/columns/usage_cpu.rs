@@ -1,5 +1,8 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::process::DataSource;
 use crate::process::ProcessInfo;
-use crate::{column_default, Column};
+use crate::util::SystemState;
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -9,10 +12,16 @@ pub struct UsageCpu {
     fmt_contents: HashMap<i32, String>,
     raw_contents: HashMap<i32, u32>,
     width: usize,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    ticks_per_second: u64,
 }
 
 impl UsageCpu {
-    pub fn new(header: Option<String>) -> Self {
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "android")),
+        allow(unused_variables)
+    )]
+    pub fn new(header: Option<String>, system_state: SystemState) -> Self {
         let header = header.unwrap_or_else(|| String::from("CPU"));
         let unit = String::from("[%]");
         Self {
@@ -21,6 +30,8 @@ impl UsageCpu {
             width: 0,
             header,
             unit,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            ticks_per_second: system_state.ticks_per_second,
         }
     }
 }
@@ -33,7 +44,7 @@ impl Column for UsageCpu {
 
         let curr_time = curr_stat.utime + curr_stat.stime;
         let prev_time = prev_stat.utime + prev_stat.stime;
-        let usage_ms = (curr_time - prev_time) * 1000 / procfs::ticks_per_second();
+        let usage_ms = (curr_time - prev_time) * 1000 / self.ticks_per_second;
         let interval_ms = proc.interval.as_secs() * 1000 + u64::from(proc.interval.subsec_millis());
         let usage = usage_ms as f64 * 100.0 / interval_ms as f64;
 
@@ -44,7 +55,11 @@ impl Column for UsageCpu {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
-    column_default!(u32, true);
+    fn data_source(&self) -> DataSource {
+        DataSource::RATE
+    }
+
+    column_default!(u32, true, 1000.0);
 }
 
 #[cfg(target_os = "macos")]
@@ -65,7 +80,7 @@ impl Column for UsageCpu {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
-    column_default!(u32, true);
+    column_default!(u32, true, 1000.0);
 }
 
 #[cfg(target_os = "windows")]
@@ -85,7 +100,7 @@ impl Column for UsageCpu {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
-    column_default!(u32, true);
+    column_default!(u32, true, 1000.0);
 }
 
 #[cfg(target_os = "freebsd")]
@@ -106,5 +121,5 @@ impl Column for UsageCpu {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
-    column_default!(u32, true);
+    column_default!(u32, true, 1000.0);
 }
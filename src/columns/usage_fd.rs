@@ -0,0 +1,71 @@
+use crate::Column;
+use crate::process::{DataSource, ProcessInfo};
+use procfs::process::LimitValue;
+use std::cmp;
+use std::collections::HashMap;
+
+pub struct UsageFd {
+    header: String,
+    unit: String,
+    fmt_contents: HashMap<i32, String>,
+    raw_contents: HashMap<i32, u32>,
+    width: usize,
+}
+
+impl UsageFd {
+    pub fn new(header: Option<String>) -> Self {
+        let header = header.unwrap_or_else(|| String::from("FD"));
+        let unit = String::from("[%]");
+        Self {
+            fmt_contents: HashMap::new(),
+            raw_contents: HashMap::new(),
+            width: 0,
+            header,
+            unit,
+        }
+    }
+}
+
+impl Column for UsageFd {
+    fn add(&mut self, proc: &ProcessInfo) {
+        // The usage is calculated against the soft limit because open(2) fails at it
+        let limit = proc.curr_proc.max_open_files().map(|x| x.soft_limit);
+        let (raw_content, fmt_content) = match (proc.curr_fd_count, limit) {
+            (Some(count), Ok(LimitValue::Value(limit))) if limit != 0 => {
+                let usage = count as f64 * 100.0 / limit as f64;
+                ((usage * 1000.0) as u32, format!("{usage:.1}"))
+            }
+            _ => (0, String::new()),
+        };
+
+        self.fmt_contents.insert(proc.pid, fmt_content);
+        self.raw_contents.insert(proc.pid, raw_content);
+    }
+
+    fn data_source(&self) -> DataSource {
+        DataSource::FD
+    }
+
+    // Usage which can't be calculated is null
+    fn display_json(&self, pid: i32) -> serde_json::Value {
+        match self.raw_value(pid) {
+            Some(x) if !self.fmt_contents[&pid].is_empty() => serde_json::Value::from(x),
+            _ => serde_json::Value::Null,
+        }
+    }
+
+    crate::column_default_display_header!();
+    crate::column_default_display_unit!();
+    crate::column_default_display_content!();
+    crate::column_default_display_raw!();
+    crate::column_default_find_partial!();
+    crate::column_default_find_exact!();
+    crate::column_default_raw_value!(1000.0);
+    crate::column_default_sorted_pid!(u32);
+    crate::column_default_apply_visible!();
+    crate::column_default_reset_width!();
+    crate::column_default_update_width!();
+    crate::column_default_get_width!();
+    crate::column_default_is_numeric!(true);
+    crate::column_default_header!();
+}
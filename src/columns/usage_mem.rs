@@ -1,7 +1,6 @@
 use crate::process::ProcessInfo;
-use crate::{column_default, Column};
-#[cfg(any(target_os = "linux", target_os = "android"))]
-use procfs::{Current, Meminfo, WithCurrentSystemInfo};
+use crate::util::SystemState;
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 #[cfg(target_os = "windows")]
@@ -16,10 +15,16 @@ pub struct UsageMem {
     raw_contents: HashMap<i32, u32>,
     width: usize,
     mem_total: u64,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    page_size: u64,
 }
 
 impl UsageMem {
-    pub fn new(header: Option<String>) -> Self {
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "android")),
+        allow(unused_variables)
+    )]
+    pub fn new(header: Option<String>, system_state: SystemState) -> Self {
         let header = header.unwrap_or_else(|| String::from("MEM"));
         let unit = String::from("[%]");
 
@@ -29,21 +34,16 @@ impl UsageMem {
             width: 0,
             header,
             unit,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            mem_total: system_state.mem_total,
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
             mem_total: get_mem_total(),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            page_size: system_state.page_size,
         }
     }
 }
 
-#[cfg(any(target_os = "linux", target_os = "android"))]
-fn get_mem_total() -> u64 {
-    let meminfo = Meminfo::current();
-    if let Ok(meminfo) = meminfo {
-        meminfo.mem_total
-    } else {
-        0
-    }
-}
-
 #[cfg(target_os = "macos")]
 fn get_mem_total() -> u64 {
     let mut mem_total: u64 = 0;
@@ -97,7 +97,8 @@ fn get_mem_total() -> u64 {
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl Column for UsageMem {
     fn add(&mut self, proc: &ProcessInfo) {
-        let usage = proc.curr_proc.stat().rss_bytes().get() as f64 * 100.0 / self.mem_total as f64;
+        let rss = proc.curr_proc.stat().rss * self.page_size;
+        let usage = rss as f64 * 100.0 / self.mem_total as f64;
         let fmt_content = format!("{usage:.1}");
         let raw_content = (usage * 1000.0) as u32;
 
@@ -105,7 +106,7 @@ impl Column for UsageMem {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
-    column_default!(u32, true);
+    column_default!(u32, true, 1000.0);
 }
 
 #[cfg(target_os = "macos")]
@@ -119,7 +120,7 @@ impl Column for UsageMem {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
-    column_default!(u32, true);
+    column_default!(u32, true, 1000.0);
 }
 
 #[cfg(target_os = "windows")]
@@ -133,7 +134,7 @@ impl Column for UsageMem {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
-    column_default!(u32, true);
+    column_default!(u32, true, 1000.0);
 }
 
 #[cfg(target_os = "freebsd")]
@@ -147,5 +148,5 @@ impl Column for UsageMem {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
-    column_default!(u32, true);
+    column_default!(u32, true, 1000.0);
 }
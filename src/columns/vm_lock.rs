@@ -1,6 +1,6 @@
-use crate::process::ProcessInfo;
+use crate::process::{DataSource, ProcessInfo};
 use crate::util::bytify;
-use crate::{column_default, Column};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -43,5 +43,9 @@ impl Column for VmLock {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::STATUS
+    }
+
     column_default!(u64, true);
 }
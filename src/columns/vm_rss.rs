@@ -1,6 +1,6 @@
 use crate::process::ProcessInfo;
-use crate::util::bytify;
-use crate::{column_default, Column};
+use crate::util::{SystemState, bytify};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -10,10 +10,16 @@ pub struct VmRss {
     fmt_contents: HashMap<i32, String>,
     raw_contents: HashMap<i32, u64>,
     width: usize,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    page_size: u64,
 }
 
 impl VmRss {
-    pub fn new(header: Option<String>) -> Self {
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "android")),
+        allow(unused_variables)
+    )]
+    pub fn new(header: Option<String>, system_state: SystemState) -> Self {
         let header = header.unwrap_or_else(|| String::from("VmRSS"));
         let unit = String::from("[bytes]");
         Self {
@@ -22,6 +28,8 @@ impl VmRss {
             width: 0,
             header,
             unit,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            page_size: system_state.page_size,
         }
     }
 }
@@ -29,8 +37,8 @@ impl VmRss {
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl Column for VmRss {
     fn add(&mut self, proc: &ProcessInfo) {
-        use procfs::WithCurrentSystemInfo;
-        let raw_content = proc.curr_proc.stat().rss_bytes().get();
+        // RSS is in pages of the system which procfs belongs to
+        let raw_content = proc.curr_proc.stat().rss * self.page_size;
         let fmt_content = bytify(raw_content);
 
         self.fmt_contents.insert(proc.pid, fmt_content);
@@ -78,3 +86,67 @@ impl Column for VmRss {
 
     column_default!(u64, true);
 }
+
+#[cfg(test)]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod tests {
+    use super::*;
+    use crate::columns::UsageMem;
+    use crate::process::ProcessTask;
+    use procfs::process::Process;
+    use std::time::Duration;
+
+    #[test]
+    fn test_procfs_page_size() {
+        // procfs of a system with 64KiB pages
+        let dir = std::env::temp_dir().join(format!("procs_page_size_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("1")).unwrap();
+        let auxv: Vec<u8> = [6u64, 65536, 17, 100, 0, 0]
+            .iter()
+            .flat_map(|x| x.to_ne_bytes())
+            .collect();
+        std::fs::write(dir.join("1").join("auxv"), auxv).unwrap();
+        let meminfo = std::fs::read_to_string("/proc/meminfo").unwrap();
+        let meminfo: Vec<_> = meminfo
+            .lines()
+            .map(|x| {
+                if x.starts_with("MemTotal:") {
+                    "MemTotal:       1280 kB"
+                } else {
+                    x
+                }
+            })
+            .collect();
+        std::fs::write(dir.join("meminfo"), meminfo.join("\n")).unwrap();
+        let procfs = Some(dir.clone());
+
+        let mut stat = Process::myself().unwrap().stat().unwrap();
+        stat.rss = 10;
+        let proc = ProcessInfo {
+            pid: 1,
+            ppid: 0,
+            curr_proc: ProcessTask::Task {
+                stat: stat.clone(),
+                owner: 0,
+            },
+            prev_stat: stat,
+            curr_io: None,
+            prev_io: None,
+            curr_status: None,
+            curr_smaps: None,
+            curr_fd_count: None,
+            interval: Duration::new(0, 0),
+        };
+
+        let system_state = crate::util::system_state(&procfs);
+        let mut vm_rss = VmRss::new(None, system_state);
+        vm_rss.add(&proc);
+        assert_eq!(vm_rss.raw_contents[&1], 10 * 65536);
+
+        let mut usage_mem = UsageMem::new(None, system_state);
+        usage_mem.add(&proc);
+        assert_eq!(usage_mem.display_raw(1).unwrap(), "50.0");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
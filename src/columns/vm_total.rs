@@ -1,6 +1,6 @@
 use crate::process::ProcessInfo;
-use crate::util::bytify;
-use crate::{column_default, Column};
+use crate::util::{SystemState, bytify};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 
@@ -10,10 +10,16 @@ pub struct VmTotal {
     fmt_contents: HashMap<i32, String>,
     raw_contents: HashMap<i32, u64>,
     width: usize,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    page_size: u64,
 }
 
 impl VmTotal {
-    pub fn new(header: Option<String>) -> Self {
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "android")),
+        allow(unused_variables)
+    )]
+    pub fn new(header: Option<String>, system_state: SystemState) -> Self {
         let header = header.unwrap_or_else(|| String::from("VmTotal"));
         let unit = String::from("[bytes]");
         Self {
@@ -22,6 +28,8 @@ impl VmTotal {
             width: 0,
             header,
             unit,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            page_size: system_state.page_size,
         }
     }
 }
@@ -29,8 +37,8 @@ impl VmTotal {
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl Column for VmTotal {
     fn add(&mut self, proc: &ProcessInfo) {
-        use procfs::WithCurrentSystemInfo;
-        let raw_content = proc.curr_proc.stat().rss_bytes().get();
+        // RSS is in pages of the system which procfs belongs to
+        let raw_content = proc.curr_proc.stat().rss * self.page_size;
         let fmt_content = bytify(raw_content);
 
         self.fmt_contents.insert(proc.pid, fmt_content);
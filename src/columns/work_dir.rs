@@ -1,5 +1,5 @@
 use crate::process::ProcessInfo;
-use crate::{column_default, Column};
+use crate::{Column, column_default};
 use std::cmp;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -31,7 +31,9 @@ impl WorkDir {
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl Column for WorkDir {
     fn add(&mut self, proc: &ProcessInfo) {
-        let fmt_content = if let Ok(proc) = crate::util::process_new(proc.pid, &self.procfs) {
+        let fmt_content = if !proc.curr_proc.is_record()
+            && let Ok(proc) = crate::util::process_new(proc.pid, &self.procfs)
+        {
             if let Ok(path) = proc.cwd() {
                 path.to_string_lossy().to_string()
             } else {
@@ -1,3 +1,5 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::process::DataSource;
 use crate::process::ProcessInfo;
 use crate::util::bytify;
 use crate::{Column, column_default};
@@ -43,6 +45,10 @@ impl Column for WriteBytes {
         self.raw_contents.insert(proc.pid, raw_content);
     }
 
+    fn data_source(&self) -> DataSource {
+        DataSource::IO.merge(DataSource::RATE)
+    }
+
     column_default!(u64, true);
 }
 
@@ -1,6 +1,7 @@
 use crate::column::Column;
-use crate::columns::ConfigColumnKind;
+use crate::columns::{ConfigColumnKind, KIND_LIST};
 use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::str::FromStr;
 
 // ---------------------------------------------------------------------------------------------------------------------
@@ -31,6 +32,48 @@ fn default_pager_mode_auto() -> ConfigPagerMode {
     ConfigPagerMode::Auto
 }
 
+// Kind list is different between platforms, so defaults are picked by name
+fn kinds_by_name(names: &[&str]) -> Vec<ConfigColumnKind> {
+    names
+        .iter()
+        .filter_map(|name| {
+            KIND_LIST
+                .iter()
+                .find(|(_, (x, _))| x == name)
+                .map(|(k, _)| k.clone())
+        })
+        .collect()
+}
+
+fn default_exporter_metrics() -> Vec<ConfigColumnKind> {
+    kinds_by_name(&[
+        "UsageCpu",
+        "UsageMem",
+        "VmRss",
+        "VmSize",
+        "ReadBytes",
+        "WriteBytes",
+        "CpuTime",
+        "ContextSw",
+    ])
+}
+
+fn default_exporter_labels() -> Vec<ConfigColumnKind> {
+    kinds_by_name(&["User"])
+}
+
+fn default_exporter_workers() -> usize {
+    4
+}
+
+fn default_diff_threads_threshold() -> u64 {
+    1
+}
+
+fn default_diff_rss_threshold() -> u64 {
+    1024 * 1024
+}
+
 fn default_search_kind_exact() -> ConfigSearchKind {
     ConfigSearchKind::Exact
 }
@@ -119,6 +162,14 @@ pub struct Config {
     pub docker: ConfigDocker,
     #[serde(default)]
     pub pager: ConfigPager,
+    #[serde(default)]
+    pub collect: ConfigCollect,
+    #[serde(default)]
+    pub exporter: ConfigExporter,
+    #[serde(default)]
+    pub diff: ConfigDiff,
+    #[serde(default)]
+    pub templates: HashMap<String, String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -311,6 +362,18 @@ pub struct ConfigColumn {
     pub max_width: Option<usize>,
     pub min_width: Option<usize>,
     pub header: Option<String>,
+    pub mode: Option<ConfigColumnMode>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ConfigColumnMode {
+    Count,
+    List,
+    Port,
+    Address,
+    Name,
+    Compact,
+    Hex,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -659,9 +722,65 @@ impl Default for ConfigPager {
     }
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ConfigCollect {
+    #[serde(default)]
+    pub parallelism: usize,
+}
+
+impl ConfigCollect {
+    /// The number of workers reading /proc. 0 means the number of available CPUs.
+    pub fn workers(&self) -> usize {
+        if self.parallelism == 0 {
+            std::thread::available_parallelism()
+                .map(|x| x.get())
+                .unwrap_or(1)
+        } else {
+            self.parallelism
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ConfigPagerMode {
     Auto,
     Always,
     Disable,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConfigExporter {
+    #[serde(default = "default_exporter_metrics")]
+    pub metrics: Vec<ConfigColumnKind>,
+    #[serde(default = "default_exporter_labels")]
+    pub labels: Vec<ConfigColumnKind>,
+    #[serde(default = "default_exporter_workers")]
+    pub workers: usize,
+}
+
+impl Default for ConfigExporter {
+    fn default() -> Self {
+        ConfigExporter {
+            metrics: default_exporter_metrics(),
+            labels: default_exporter_labels(),
+            workers: default_exporter_workers(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    #[serde(default = "default_diff_threads_threshold")]
+    pub threads_threshold: u64,
+    #[serde(default = "default_diff_rss_threshold")]
+    pub rss_threshold: u64,
+}
+
+impl Default for ConfigDiff {
+    fn default() -> Self {
+        ConfigDiff {
+            threads_threshold: default_diff_threads_threshold(),
+            rss_threshold: default_diff_rss_threshold(),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::Opt;
+use crate::column::{Column, RawValue};
+use crate::columns::*;
+use crate::config::*;
+use crate::process::ProcessInfo;
+use crate::util::SystemState;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    Appeared,
+    Disappeared,
+    Changed,
+}
+
+impl DiffKind {
+    pub fn marker(&self) -> &'static str {
+        match self {
+            DiffKind::Appeared => "+",
+            DiffKind::Disappeared => "-",
+            DiffKind::Changed => "~",
+        }
+    }
+}
+
+// Kinds compared between two samples
+const DIFF_KINDS: [&str; 4] = ["State", "Threads", "VmRss", "Command"];
+
+// Kind which identifies a process with PID because PIDs may be reused
+const START_KIND: &str = "StartTime";
+
+type Summary = Vec<Option<RawValue>>;
+
+// PID and start time in milliseconds
+type ProcessKey = (i32, Option<i64>);
+
+/// Processes and the system state when they are collected or recorded
+pub type Baseline = (Vec<ProcessInfo>, SystemState);
+
+fn summarize(
+    procs: &[ProcessInfo],
+    system_state: SystemState,
+    opt: &Opt,
+    config: &Config,
+) -> HashMap<ProcessKey, Summary> {
+    let mut columns: Vec<Option<Box<dyn Column>>> = [START_KIND]
+        .iter()
+        .chain(DIFF_KINDS.iter())
+        .map(|name| {
+            let (kind, _) = KIND_LIST.iter().find(|(_, (x, _))| x == name)?;
+            let column = gen_column(
+                kind,
+                None,
+                &config.docker.path,
+                &config.display.separator,
+                config.display.abbr_sid,
+                &config.display.tree_symbols,
+                opt.procfs.clone(),
+                system_state,
+            );
+            column.available().then_some(column)
+        })
+        .collect();
+
+    for c in columns.iter_mut().flatten() {
+        for p in procs {
+            c.add(p);
+        }
+    }
+
+    let start = columns.remove(0);
+    procs
+        .iter()
+        .map(|p| {
+            let start = start
+                .as_ref()
+                .and_then(|c| c.raw_value(p.pid))
+                .and_then(|x| x.as_f64())
+                .map(|x| (x * 1000.0).round() as i64);
+            let summary = columns
+                .iter()
+                .map(|c| c.as_ref().and_then(|c| c.raw_value(p.pid)))
+                .collect();
+            ((p.pid, start), summary)
+        })
+        .collect()
+}
+
+fn changed(name: &str, prev: &Option<RawValue>, curr: &Option<RawValue>, config: &Config) -> bool {
+    match (prev, curr) {
+        (Some(RawValue::Text(x)), Some(RawValue::Text(y))) => x != y,
+        (Some(x), Some(y)) => {
+            let (x, y) = match (x.as_f64(), y.as_f64()) {
+                (Some(x), Some(y)) => (x, y),
+                _ => return false,
+            };
+            let threshold = match name {
+                "Threads" => config.diff.threads_threshold,
+                "VmRss" => config.diff.rss_threshold,
+                _ => 0,
+            };
+            (x - y).abs() >= (threshold as f64).max(1.0)
+        }
+        _ => false,
+    }
+}
+
+/// Compare processes with the baseline, and return appeared, disappeared and changed processes.
+/// A process is identified by PID and start time, so a reused PID is reported as disappeared and appeared.
+/// Processes are given with the system state when they are collected or recorded.
+pub fn compare(
+    baseline: (&[ProcessInfo], SystemState),
+    procs: (&[ProcessInfo], SystemState),
+    opt: &Opt,
+    config: &Config,
+) -> Vec<(i32, DiffKind)> {
+    let prev = summarize(baseline.0, baseline.1, opt, config);
+    let curr = summarize(procs.0, procs.1, opt, config);
+    compare_summary(&prev, &curr, config)
+}
+
+fn compare_summary(
+    prev: &HashMap<ProcessKey, Summary>,
+    curr: &HashMap<ProcessKey, Summary>,
+    config: &Config,
+) -> Vec<(i32, DiffKind)> {
+    let mut ret = Vec::new();
+    for (key, y) in curr {
+        if let Some(x) = prev.get(key) {
+            let is_changed = DIFF_KINDS
+                .iter()
+                .zip(x.iter().zip(y.iter()))
+                .any(|(name, (x, y))| changed(name, x, y, config));
+            if is_changed {
+                ret.push((key.0, DiffKind::Changed));
+            }
+        } else {
+            ret.push((key.0, DiffKind::Appeared));
+        }
+    }
+    for key in prev.keys() {
+        if !curr.contains_key(key) {
+            ret.push((key.0, DiffKind::Disappeared));
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed() {
+        let config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
+        let num = |x| Some(RawValue::U64(x));
+        let text = |x: &str| Some(RawValue::Text(x.to_string()));
+
+        assert!(changed("Threads", &num(1), &num(2), &config));
+        assert!(!changed("Threads", &num(1), &num(1), &config));
+        assert!(!changed("VmRss", &num(0), &num(1024), &config));
+        assert!(changed("VmRss", &num(0), &num(64 * 1024 * 1024), &config));
+        assert!(changed("State", &text("S"), &text("R"), &config));
+        assert!(!changed("Command", &text("a"), &text("a"), &config));
+        assert!(!changed("Command", &None, &text("a"), &config));
+    }
+
+    #[test]
+    fn test_compare_summary() {
+        let config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
+        let summary = |state: &str| {
+            let mut x = vec![None; DIFF_KINDS.len()];
+            x[0] = Some(RawValue::Text(state.to_string()));
+            x
+        };
+        let sorted = |mut x: Vec<(i32, DiffKind)>| {
+            x.sort_by_key(|(pid, kind)| (*pid, kind.marker()));
+            x
+        };
+
+        let prev = HashMap::from([
+            ((1, Some(100)), summary("S")),
+            ((2, Some(200)), summary("S")),
+            ((3, Some(300)), summary("S")),
+        ]);
+        let curr = HashMap::from([
+            ((1, Some(100)), summary("S")),
+            ((2, Some(200)), summary("R")),
+            ((4, Some(400)), summary("S")),
+        ]);
+        assert_eq!(
+            sorted(compare_summary(&prev, &curr, &config)),
+            vec![
+                (2, DiffKind::Changed),
+                (3, DiffKind::Disappeared),
+                (4, DiffKind::Appeared)
+            ]
+        );
+
+        // PID 1 is reused by another process
+        let curr = HashMap::from([((1, Some(500)), summary("S"))]);
+        let prev = HashMap::from([((1, Some(100)), summary("S"))]);
+        assert_eq!(
+            sorted(compare_summary(&prev, &curr, &config)),
+            vec![(1, DiffKind::Appeared), (1, DiffKind::Disappeared)]
+        );
+    }
+}
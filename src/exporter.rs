@@ -0,0 +1,462 @@
+use crate::Opt;
+use crate::column::{Column, RawValue};
+use crate::columns::*;
+use crate::config::*;
+use crate::process::{DataSource, collect_proc};
+use anyhow::Error;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Mutex, mpsc};
+use std::thread;
+use std::time::Duration;
+
+// Kinds which are accumulated over the process lifetime.
+// They are counters only if each process has its own series because a sum over processes drops when a process exits.
+const COUNTER_KINDS: [&str; 6] = [
+    "CpuTime",
+    "ContextSw",
+    "VoluntaryContextSw",
+    "InvoluntaryContextSw",
+    "MajFlt",
+    "MinFlt",
+];
+
+// Kinds which are in bytes
+const BYTES_KINDS: [&str; 18] = [
+    "Pss",
+    "SharedClean",
+    "SharedDirty",
+    "SwapPss",
+    "Uss",
+    "VmData",
+    "VmExe",
+    "VmHwm",
+    "VmLib",
+    "VmLock",
+    "VmPeak",
+    "VmPin",
+    "VmPte",
+    "VmRss",
+    "VmSize",
+    "VmStack",
+    "VmSwap",
+    "VmTotal",
+];
+
+pub struct Exporter;
+
+impl Exporter {
+    pub fn start(opt: &Opt, config: &Config, addr: &str) -> Result<(), Error> {
+        let listener = TcpListener::bind(addr)?;
+        eprintln!(
+            "Serving metrics on http://{}/metrics",
+            listener.local_addr()?
+        );
+
+        // Connections are handled by a fixed number of workers so that a slow client doesn't block other scrapes.
+        // Accepting waits while all workers are busy and the queue is full.
+        let workers = config.exporter.workers.max(1);
+        let (tx, rx) = mpsc::sync_channel::<TcpStream>(workers);
+        let rx = Mutex::new(rx);
+        thread::scope(|s| {
+            for _ in 0..workers {
+                let rx = &rx;
+                s.spawn(move || {
+                    loop {
+                        let stream = rx.lock().unwrap().recv();
+                        let Ok(mut stream) = stream else {
+                            break;
+                        };
+                        if let Err(e) = Exporter::handle(&mut stream, opt, config) {
+                            eprintln!("Failed to handle request: {e}");
+                        }
+                    }
+                });
+            }
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                if tx.send(stream).is_err() {
+                    break;
+                }
+            }
+            drop(tx);
+        });
+        Ok(())
+    }
+
+    fn handle(stream: &mut TcpStream, opt: &Opt, config: &Config) -> Result<(), Error> {
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request = String::new();
+        reader.read_line(&mut request)?;
+
+        // Skip headers
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                break;
+            }
+        }
+
+        let mut request = request.split_whitespace();
+        let method = request.next().unwrap_or_default();
+        // Query is ignored
+        let path = request.next().unwrap_or_default();
+        let path = path.split_once('?').map_or(path, |(x, _)| x);
+
+        let (status, content_type, body) = match (method, path) {
+            ("GET", "/metrics") => (
+                "200 OK",
+                "text/plain; version=0.0.4; charset=utf-8",
+                Exporter::render(opt, config),
+            ),
+            ("GET", "/") => (
+                "200 OK",
+                "text/html; charset=utf-8",
+                String::from("<html><body><a href=\"/metrics\">Metrics</a></body></html>\n"),
+            ),
+            _ => (
+                "404 Not Found",
+                "text/plain; charset=utf-8",
+                String::from("Not Found\n"),
+            ),
+        };
+
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        )?;
+        stream.flush()?;
+        Ok(())
+    }
+
+    pub fn render(opt: &Opt, config: &Config) -> String {
+        let system_state = crate::util::system_state(&opt.procfs);
+        let mut columns: Vec<(ConfigColumnKind, Box<dyn Column>)> = Vec::new();
+        for kind in config
+            .exporter
+            .metrics
+            .iter()
+            .chain(config.exporter.labels.iter())
+        {
+            if columns.iter().any(|(k, _)| k == kind) {
+                continue;
+            }
+            let column = gen_column(
+                kind,
+                None,
+                &config.docker.path,
+                &config.display.separator,
+                config.display.abbr_sid,
+                &config.display.tree_symbols,
+                opt.procfs.clone(),
+                system_state,
+            );
+            if column.available() {
+                columns.push((kind.clone(), column));
+            }
+        }
+
+        let data_source = columns
+            .iter()
+            .fold(DataSource::default(), |x, (_, c)| x.merge(c.data_source()));
+        let proc = collect_proc(
+            Duration::from_millis(opt.interval),
+            false,
+            config.display.show_kthreads,
+            &opt.procfs,
+            config.collect.workers(),
+            data_source,
+            false,
+        );
+        for (_, c) in columns.iter_mut() {
+            for p in &proc {
+                c.add(p);
+            }
+        }
+
+        let pids: Vec<_> = proc.iter().map(|p| p.pid).collect();
+        Exporter::format(&columns, &pids, config)
+    }
+
+    fn format(
+        columns: &[(ConfigColumnKind, Box<dyn Column>)],
+        pids: &[i32],
+        config: &Config,
+    ) -> String {
+        // Processes which have the same labels are summed into one series.
+        // `Pid` label makes each process its own series.
+        let per_process = config.exporter.labels.contains(&ConfigColumnKind::Pid);
+        let labels: Vec<_> = pids
+            .iter()
+            .map(|pid| {
+                let mut label = Vec::new();
+                for kind in &config.exporter.labels {
+                    if let Some((_, c)) = columns.iter().find(|(k, _)| k == kind) {
+                        let value = c.display_raw(*pid).unwrap_or_default();
+                        label.push(format!(
+                            "{}=\"{}\"",
+                            snake_case(KIND_LIST[kind].0),
+                            escape_label(&value)
+                        ));
+                    }
+                }
+                (*pid, label.join(","))
+            })
+            .collect();
+
+        let mut ret = String::new();
+        for kind in &config.exporter.metrics {
+            let Some((_, c)) = columns.iter().find(|(k, _)| k == kind) else {
+                continue;
+            };
+            if !c.is_numeric() {
+                continue;
+            }
+
+            let (name, desc) = KIND_LIST[kind];
+            let counter = per_process && COUNTER_KINDS.contains(&name);
+            let metric = metric_name(name, counter);
+            let _ = writeln!(ret, "# HELP {metric} {desc}");
+            let _ = writeln!(
+                ret,
+                "# TYPE {} {}",
+                metric,
+                if counter { "counter" } else { "gauge" }
+            );
+
+            let mut series: BTreeMap<&str, RawValue> = BTreeMap::new();
+            for (pid, label) in &labels {
+                let Some(x) = c.raw_value(*pid).filter(|x| x.as_f64().is_some()) else {
+                    continue;
+                };
+                let x = match series.remove(label.as_str()) {
+                    Some(y) => sum(&y, &x),
+                    None => x,
+                };
+                series.insert(label, x);
+            }
+            for (label, x) in series {
+                if label.is_empty() {
+                    let _ = writeln!(ret, "{metric} {x}");
+                } else {
+                    let _ = writeln!(ret, "{metric}{{{label}}} {x}");
+                }
+            }
+        }
+        ret
+    }
+}
+
+fn snake_case(x: &str) -> String {
+    let mut ret = String::new();
+    for (i, c) in x.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i != 0 {
+                ret.push('_');
+            }
+            ret.push(c.to_ascii_lowercase());
+        } else {
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+/// Metric name with unit suffix like `procs_cpu_time_seconds_total`
+fn metric_name(name: &str, counter: bool) -> String {
+    let mut ret = format!("procs_{}", snake_case(name));
+    if name == "CpuTime" {
+        ret.push_str("_seconds");
+    } else if name == "ReadBytes" || name == "WriteBytes" {
+        ret.push_str("_per_second");
+    } else if name == "UsageCpu" || name == "UsageMem" {
+        ret.push_str("_percent");
+    } else if BYTES_KINDS.contains(&name) {
+        ret.push_str("_bytes");
+    }
+    if counter {
+        ret.push_str("_total");
+    }
+    ret
+}
+
+fn sum(x: &RawValue, y: &RawValue) -> RawValue {
+    match (x, y) {
+        (RawValue::U64(x), RawValue::U64(y)) => RawValue::U64(x.saturating_add(*y)),
+        (RawValue::I64(x), RawValue::I64(y)) => RawValue::I64(x.saturating_add(*y)),
+        _ => RawValue::Number(x.as_f64().unwrap_or(0.0) + y.as_f64().unwrap_or(0.0)),
+    }
+}
+
+fn escape_label(x: &str) -> String {
+    x.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("UsageCpu"), "usage_cpu");
+        assert_eq!(snake_case("VmRss"), "vm_rss");
+        assert_eq!(snake_case("Command"), "command");
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    #[test]
+    fn test_metric_name() {
+        assert_eq!(metric_name("CpuTime", true), "procs_cpu_time_seconds_total");
+        assert_eq!(metric_name("CpuTime", false), "procs_cpu_time_seconds");
+        assert_eq!(metric_name("VmRss", false), "procs_vm_rss_bytes");
+        assert_eq!(
+            metric_name("ReadBytes", false),
+            "procs_read_bytes_per_second"
+        );
+        assert_eq!(metric_name("UsageCpu", false), "procs_usage_cpu_percent");
+        assert_eq!(metric_name("ContextSw", true), "procs_context_sw_total");
+        assert_eq!(metric_name("Threads", false), "procs_threads");
+    }
+
+    #[test]
+    fn test_sum() {
+        let x = sum(&RawValue::U64(u64::MAX - 1), &RawValue::U64(1));
+        assert_eq!(x, RawValue::U64(u64::MAX));
+        let x = sum(&RawValue::Number(0.5), &RawValue::Number(1.0));
+        assert_eq!(x, RawValue::Number(1.5));
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_render() {
+        let mut config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
+        let opt = Opt::parse_from(["procs"].iter());
+        let metrics = Exporter::render(&opt, &config);
+        assert!(metrics.contains("# TYPE procs_usage_cpu_percent gauge"));
+        // Summed CpuTime drops when a process exits, so it is not a counter
+        assert!(metrics.contains("# TYPE procs_cpu_time_seconds gauge"));
+        assert!(!metrics.contains("pid=\""));
+
+        // Processes are summed by user
+        let series = |metrics: &str, prefix: &str| {
+            metrics
+                .lines()
+                .filter(|x| x.starts_with(prefix))
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+        };
+        let users: HashSet<_> = series(&metrics, "procs_vm_rss_bytes{")
+            .iter()
+            .map(|x| x.split_once(' ').unwrap().0.to_string())
+            .collect();
+        assert_eq!(users.len(), series(&metrics, "procs_vm_rss_bytes{").len());
+        assert!(!users.is_empty());
+        assert!(
+            users
+                .iter()
+                .all(|x| x.starts_with("procs_vm_rss_bytes{user=\""))
+        );
+
+        // Each process has its own series with Pid label
+        let pid = std::process::id();
+        config.exporter.labels = vec![ConfigColumnKind::Pid, ConfigColumnKind::User];
+        let metrics = Exporter::render(&opt, &config);
+        assert!(metrics.contains(&format!("procs_vm_rss_bytes{{pid=\"{pid}\",user=\"")));
+        assert!(metrics.contains("# TYPE procs_cpu_time_seconds_total counter"));
+        assert!(series(&metrics, "procs_vm_rss_bytes{").len() > 1);
+
+        // All processes are summed into one series without labels
+        config.exporter.labels = Vec::new();
+        let metrics = Exporter::render(&opt, &config);
+        let rss = series(&metrics, "procs_vm_rss_bytes");
+        assert_eq!(rss.len(), 1);
+        assert!(
+            rss[0]
+                .strip_prefix("procs_vm_rss_bytes ")
+                .unwrap()
+                .parse::<u64>()
+                .unwrap()
+                > 0
+        );
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_format_process_exit() {
+        let mut config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
+        config.exporter.metrics = vec![ConfigColumnKind::CpuTime];
+
+        let proc = collect_proc(
+            Duration::from_millis(0),
+            false,
+            false,
+            &None,
+            1,
+            DataSource::default(),
+            false,
+        );
+        let mut columns: Vec<(ConfigColumnKind, Box<dyn Column>)> = Vec::new();
+        for kind in [
+            ConfigColumnKind::CpuTime,
+            ConfigColumnKind::Pid,
+            ConfigColumnKind::User,
+        ] {
+            let mut column = gen_column(
+                &kind,
+                None,
+                &config.docker.path,
+                &config.display.separator,
+                config.display.abbr_sid,
+                &config.display.tree_symbols,
+                None,
+                crate::util::system_state(&None),
+            );
+            for p in &proc {
+                column.add(p);
+            }
+            columns.push((kind, column));
+        }
+        let pids: Vec<_> = proc.iter().map(|p| p.pid).collect();
+        let self_pid = std::process::id() as i32;
+        let exited: Vec<_> = pids.iter().copied().filter(|x| *x != self_pid).collect();
+
+        // The sum by user drops when this process exits, so it is a gauge
+        config.exporter.labels = vec![ConfigColumnKind::User];
+        for pids in [&pids, &exited] {
+            let metrics = Exporter::format(&columns, pids, &config);
+            assert!(metrics.contains("# TYPE procs_cpu_time_seconds gauge"));
+            assert!(!metrics.contains("_total"));
+        }
+
+        // Each series of a counter is kept or removed, but never decreases
+        config.exporter.labels = vec![ConfigColumnKind::Pid, ConfigColumnKind::User];
+        let before = Exporter::format(&columns, &pids, &config);
+        let after = Exporter::format(&columns, &exited, &config);
+        assert!(before.contains("# TYPE procs_cpu_time_seconds_total counter"));
+        let series = format!("procs_cpu_time_seconds_total{{pid=\"{self_pid}\"");
+        assert!(before.contains(&series));
+        assert!(!after.contains(&series));
+        for line in after.lines().filter(|x| !x.starts_with('#')) {
+            assert!(before.lines().any(|x| x == line));
+        }
+    }
+}
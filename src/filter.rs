@@ -0,0 +1,599 @@
+use crate::column::RawValue;
+use crate::columns::{ConfigColumnKind, KIND_LIST};
+use crate::config::{ColumnInfo, ConfigSearchCase};
+use crate::search_regex::SearchRegex;
+use anyhow::{Error, anyhow, bail};
+use std::cmp::Ordering;
+
+// ---------------------------------------------------------------------------------------------------------------------
+// Token
+// ---------------------------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Op(CompareOp),
+    Str(String),
+    Word(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Match,
+    NotMatch,
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, Error> {
+    let mut ret = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let next = chars.peek().copied();
+        let token = match (c, next) {
+            (c, _) if c.is_whitespace() => continue,
+            ('(', _) => Token::LParen,
+            (')', _) => Token::RParen,
+            ('&', Some('&')) => {
+                chars.next();
+                Token::And
+            }
+            ('|', Some('|')) => {
+                chars.next();
+                Token::Or
+            }
+            ('=', Some('=')) => {
+                chars.next();
+                Token::Op(CompareOp::Eq)
+            }
+            ('=', Some('~')) => {
+                chars.next();
+                Token::Op(CompareOp::Match)
+            }
+            ('=', _) => Token::Op(CompareOp::Eq),
+            ('!', Some('=')) => {
+                chars.next();
+                Token::Op(CompareOp::Ne)
+            }
+            ('!', Some('~')) => {
+                chars.next();
+                Token::Op(CompareOp::NotMatch)
+            }
+            ('!', _) => Token::Not,
+            ('<', Some('=')) => {
+                chars.next();
+                Token::Op(CompareOp::Le)
+            }
+            ('<', _) => Token::Op(CompareOp::Lt),
+            ('>', Some('=')) => {
+                chars.next();
+                Token::Op(CompareOp::Ge)
+            }
+            ('>', _) => Token::Op(CompareOp::Gt),
+            ('"', _) | ('\'', _) => {
+                let quote = c;
+                let mut buf = String::new();
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    if c == '\\' {
+                        if let Some(c) = chars.next() {
+                            buf.push(c);
+                        }
+                    } else if c == quote {
+                        closed = true;
+                        break;
+                    } else {
+                        buf.push(c);
+                    }
+                }
+                if !closed {
+                    bail!("unterminated string literal in filter: {}{}", quote, buf);
+                }
+                Token::Str(buf)
+            }
+            ('&', _) | ('|', _) => bail!("unexpected character in filter: '{}'", c),
+            (c, _) => {
+                let mut buf = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "()&|=!<>\"'".contains(c) {
+                        break;
+                    }
+                    buf.push(c);
+                    chars.next();
+                }
+                Token::Word(buf)
+            }
+        };
+        ret.push(token);
+    }
+    Ok(ret)
+}
+
+// ---------------------------------------------------------------------------------------------------------------------
+// Literal
+// ---------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct Literal {
+    pub text: String,
+    pub number: Option<f64>,
+}
+
+impl Literal {
+    fn new(text: String, quoted: bool) -> Self {
+        let number = if quoted { None } else { parse_number(&text) };
+        Literal { text, number }
+    }
+}
+
+fn unit_scale(unit: &str) -> Option<f64> {
+    // Byte units are case-insensitive, so `m` is MiB and minutes is `min`
+    let scale = match unit.to_ascii_lowercase().as_str() {
+        "" | "%" => 1.0,
+        "k" | "kb" | "kib" => 1024.0,
+        "m" | "mb" | "mib" => 1024.0 * 1024.0,
+        "g" | "gb" | "gib" => 1024.0 * 1024.0 * 1024.0,
+        "t" | "tb" | "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "p" | "pb" | "pib" => 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return duration_scale(unit),
+    };
+    Some(scale)
+}
+
+fn duration_scale(unit: &str) -> Option<f64> {
+    let scale = match unit {
+        "ms" => 0.001,
+        "s" => 1.0,
+        "min" => 60.0,
+        "h" => 60.0 * 60.0,
+        "d" => 60.0 * 60.0 * 24.0,
+        "w" => 60.0 * 60.0 * 24.0 * 7.0,
+        _ => return None,
+    };
+    Some(scale)
+}
+
+/// Parse a number with an optional unit suffix.
+///
+/// Byte units ( `K`, `M`, `G`, `T`, `P` ) are binary and case-insensitive,
+/// duration units ( `ms`, `s`, `min`, `h`, `d`, `w` ) are converted to seconds and can be chained like `1h30min`.
+pub fn parse_number(s: &str) -> Option<f64> {
+    let mut total = 0.0;
+    let mut rest = s;
+    let mut parts = 0;
+    let sign = if let Some(x) = rest.strip_prefix('-') {
+        rest = x;
+        -1.0
+    } else {
+        1.0
+    };
+    while !rest.is_empty() || parts == 0 {
+        let num_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (num, tail) = rest.split_at(num_len);
+        let num: f64 = num.parse().ok()?;
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let scale = unit_scale(unit)?;
+        if (parts > 0 || !tail.is_empty()) && duration_scale(unit).is_none() {
+            return None;
+        }
+        total += num * scale;
+        rest = tail;
+        parts += 1;
+    }
+    Some(sign * total)
+}
+
+// ---------------------------------------------------------------------------------------------------------------------
+// Expr
+// ---------------------------------------------------------------------------------------------------------------------
+
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare {
+        kind: ConfigColumnKind,
+        op: CompareOp,
+        literal: Literal,
+        regex: Option<SearchRegex>,
+    },
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    case: &'a ConfigSearchCase,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let ret = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        ret
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            let rhs = self.parse_and()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            let rhs = self.parse_unary()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, Error> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            let expr = self.parse_unary()?;
+            Ok(Expr::Not(Box::new(expr)))
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, Error> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => bail!("missing ')' in filter"),
+                }
+            }
+            Some(Token::Word(name)) => {
+                let kind = find_filter_kind(&name)
+                    .ok_or_else(|| anyhow!("unknown column kind in filter: {}", name))?;
+                let op = match self.next() {
+                    Some(Token::Op(op)) => op,
+                    _ => bail!("expected comparison operator after \"{}\" in filter", name),
+                };
+                let literal = match self.next() {
+                    Some(Token::Word(x)) => Literal::new(x, false),
+                    Some(Token::Str(x)) => Literal::new(x, true),
+                    _ => bail!("expected value after \"{}\" in filter", name),
+                };
+                let regex = if matches!(op, CompareOp::Match | CompareOp::NotMatch) {
+                    let ignore_case = match self.case {
+                        ConfigSearchCase::Smart => {
+                            literal.text == literal.text.to_ascii_lowercase()
+                        }
+                        ConfigSearchCase::Insensitive => true,
+                        ConfigSearchCase::Sensitive => false,
+                    };
+                    Some(SearchRegex::new(&literal.text, ignore_case)?)
+                } else {
+                    None
+                };
+                Ok(Expr::Compare {
+                    kind,
+                    op,
+                    literal,
+                    regex,
+                })
+            }
+            Some(x) => bail!("unexpected token in filter: {:?}", x),
+            None => bail!("unexpected end of filter"),
+        }
+    }
+}
+
+/// Find column kind for filter identifier.
+///
+/// The identifier is matched with kind name case-insensitively:
+/// exact match at first, suffix match ( `cpu` -> `UsageCpu` ) at second, and partial match at last.
+pub fn find_filter_kind(pat: &str) -> Option<ConfigColumnKind> {
+    let pat = pat.to_lowercase();
+    let matchers: [&dyn Fn(&str) -> bool; 3] = [
+        &|x: &str| x == pat,
+        &|x: &str| x.ends_with(&pat),
+        &|x: &str| x.contains(&pat),
+    ];
+    for matcher in matchers {
+        for (k, (v, _)) in KIND_LIST.iter() {
+            if matcher(&v.to_lowercase()) {
+                return Some(k.clone());
+            }
+        }
+    }
+    None
+}
+
+impl Expr {
+    pub fn parse(s: &str, case: &ConfigSearchCase) -> Result<Self, Error> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            case,
+        };
+        let expr = parser.parse_or()?;
+        if let Some(x) = parser.peek() {
+            bail!("unexpected token in filter: {:?}", x);
+        }
+        Ok(expr)
+    }
+
+    pub fn kinds(&self, kinds: &mut Vec<ConfigColumnKind>) {
+        match self {
+            Expr::And(x, y) | Expr::Or(x, y) => {
+                x.kinds(kinds);
+                y.kinds(kinds);
+            }
+            Expr::Not(x) => x.kinds(kinds),
+            Expr::Compare { kind, .. } => {
+                if !kinds.contains(kind) {
+                    kinds.push(kind.clone());
+                }
+            }
+        }
+    }
+
+    pub fn eval(&self, pid: i32, columns: &[ColumnInfo]) -> Result<bool, Error> {
+        match self {
+            Expr::And(x, y) => Ok(x.eval(pid, columns)? && y.eval(pid, columns)?),
+            Expr::Or(x, y) => Ok(x.eval(pid, columns)? || y.eval(pid, columns)?),
+            Expr::Not(x) => Ok(!x.eval(pid, columns)?),
+            Expr::Compare {
+                kind,
+                op,
+                literal,
+                regex,
+            } => {
+                let column = if let Some(c) = columns.iter().find(|c| c.kind == *kind) {
+                    c.column.as_ref()
+                } else {
+                    return Ok(false);
+                };
+                let value = if let Some(x) = column.raw_value(pid) {
+                    x
+                } else {
+                    return Ok(false);
+                };
+
+                if let Some(regex) = regex {
+                    let hit = regex.is_match(&value.to_string())?;
+                    return Ok(if *op == CompareOp::Match { hit } else { !hit });
+                }
+
+                let ordering = match (&value, literal.number) {
+                    (RawValue::Text(x), _) if matches!(op, CompareOp::Eq | CompareOp::Ne) => {
+                        let hit =
+                            *x == literal.text || column.find_exact(pid, &literal.text, false);
+                        return Ok(if *op == CompareOp::Eq { hit } else { !hit });
+                    }
+                    (RawValue::Text(x), Some(y)) => {
+                        x.trim().parse::<f64>().ok().and_then(|x| x.partial_cmp(&y))
+                    }
+                    (RawValue::Text(x), None) => Some(x.as_str().cmp(literal.text.as_str())),
+                    (x, Some(y)) => x.as_f64().and_then(|x| x.partial_cmp(&y)),
+                    (_, None) => None,
+                };
+
+                let ordering = if let Some(x) = ordering {
+                    x
+                } else {
+                    return Ok(*op == CompareOp::Ne);
+                };
+                let ret = match op {
+                    CompareOp::Eq => ordering == Ordering::Equal,
+                    CompareOp::Ne => ordering != Ordering::Equal,
+                    CompareOp::Lt => ordering == Ordering::Less,
+                    CompareOp::Le => ordering != Ordering::Greater,
+                    CompareOp::Gt => ordering == Ordering::Greater,
+                    CompareOp::Ge => ordering != Ordering::Less,
+                    CompareOp::Match | CompareOp::NotMatch => unreachable!(),
+                };
+                Ok(ret)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::Column;
+    use crate::config::{ConfigColumnAlign, ConfigColumnStyle};
+    use crate::{column_default, process::ProcessInfo};
+    use std::cmp;
+    use std::collections::HashMap;
+
+    struct NumColumn {
+        header: String,
+        unit: String,
+        fmt_contents: HashMap<i32, String>,
+        raw_contents: HashMap<i32, u64>,
+        width: usize,
+    }
+
+    impl Column for NumColumn {
+        fn add(&mut self, _proc: &ProcessInfo) {}
+        column_default!(u64, true);
+    }
+
+    struct TextColumn {
+        header: String,
+        unit: String,
+        fmt_contents: HashMap<i32, String>,
+        raw_contents: HashMap<i32, String>,
+        width: usize,
+    }
+
+    impl Column for TextColumn {
+        fn add(&mut self, _proc: &ProcessInfo) {}
+        column_default!(String, false);
+    }
+
+    fn column_info(kind: ConfigColumnKind, column: Box<dyn Column>) -> ColumnInfo {
+        ColumnInfo {
+            column,
+            kind,
+            style: ConfigColumnStyle::ByUnit,
+            nonnumeric_search: false,
+            numeric_search: false,
+            align: ConfigColumnAlign::Left,
+            max_width: None,
+            min_width: None,
+            visible: true,
+        }
+    }
+
+    // PID 3 has no value in any column
+    fn columns() -> Vec<ColumnInfo> {
+        let rss = NumColumn {
+            header: String::new(),
+            unit: String::new(),
+            fmt_contents: HashMap::from([(1, "1.0M".into()), (2, "2.0G".into())]),
+            raw_contents: HashMap::from([(1, 1024 * 1024), (2, 2 * 1024 * 1024 * 1024)]),
+            width: 0,
+        };
+        let user = TextColumn {
+            header: String::new(),
+            unit: String::new(),
+            fmt_contents: HashMap::from([(1, "root".into()), (2, "www-data".into())]),
+            raw_contents: HashMap::from([(1, "root".into()), (2, "www-data".into())]),
+            width: 0,
+        };
+        // Displayed content differs from the raw value
+        let command = TextColumn {
+            header: String::new(),
+            unit: String::new(),
+            fmt_contents: HashMap::from([(1, "pts/1".into()), (2, "42".into())]),
+            raw_contents: HashMap::from([(1, "/dev/pts/1".into()), (2, "42".into())]),
+            width: 0,
+        };
+        vec![
+            column_info(ConfigColumnKind::VmRss, Box::new(rss)),
+            column_info(ConfigColumnKind::User, Box::new(user)),
+            column_info(ConfigColumnKind::Command, Box::new(command)),
+        ]
+    }
+
+    fn matched(filter: &str) -> Vec<i32> {
+        let columns = columns();
+        let expr = Expr::parse(filter, &ConfigSearchCase::Smart).unwrap();
+        (1..=3)
+            .filter(|pid| expr.eval(*pid, &columns).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("20"), Some(20.0));
+        assert_eq!(parse_number("-1"), Some(-1.0));
+        assert_eq!(parse_number("0.5%"), Some(0.5));
+        assert_eq!(parse_number("512M"), Some(512.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_number("1KiB"), Some(1024.0));
+        assert_eq!(parse_number("512m"), Some(512.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_number("1kb"), Some(1024.0));
+        assert_eq!(parse_number("1h30min"), Some(5400.0));
+        assert_eq!(parse_number("2min"), Some(120.0));
+        assert_eq!(parse_number("250ms"), Some(0.25));
+        assert_eq!(parse_number("1h30m"), None);
+        assert_eq!(parse_number("1M30s"), None);
+        assert_eq!(parse_number("root"), None);
+        assert_eq!(parse_number("10.0.3.7"), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let case = ConfigSearchCase::Smart;
+        let expr = Expr::parse(
+            "cpu > 20 && (user == \"www-data\" || !(rss >= 512M))",
+            &case,
+        )
+        .unwrap();
+        let mut kinds = Vec::new();
+        expr.kinds(&mut kinds);
+        assert_eq!(
+            kinds,
+            vec![
+                ConfigColumnKind::UsageCpu,
+                ConfigColumnKind::User,
+                ConfigColumnKind::VmRss
+            ]
+        );
+
+        assert!(Expr::parse("cpu >", &case).is_err());
+        assert!(Expr::parse("cpu > 20 &&", &case).is_err());
+        assert!(Expr::parse("(cpu > 20", &case).is_err());
+        assert!(Expr::parse("cpu > 20 mem", &case).is_err());
+        assert!(Expr::parse("command == \"abc", &case).is_err());
+        assert!(Expr::parse("nosuchcolumn == 1", &case).is_err());
+        assert!(Expr::parse("command =~ \"(\"", &case).is_err());
+    }
+
+    #[test]
+    fn test_eval() {
+        // Numeric columns are compared with the value in the unit
+        assert_eq!(matched("rss > 512M"), vec![2]);
+        assert_eq!(matched("rss > 512m"), vec![2]);
+        assert_eq!(matched("rss <= 1M"), vec![1]);
+        assert_eq!(matched("rss == 1048576"), vec![1]);
+        // Non-numeric literal is not equal to any number
+        assert_eq!(matched("rss == abc"), Vec::<i32>::new());
+        assert_eq!(matched("rss != abc"), vec![1, 2]);
+
+        // Text columns are compared as text
+        assert_eq!(matched("user == root"), vec![1]);
+        assert_eq!(matched("user != root"), vec![2]);
+        assert_eq!(matched("user < \"s\""), vec![1]);
+        assert_eq!(matched("user =~ \"^www\""), vec![2]);
+        assert_eq!(matched("user !~ \"^www\""), vec![1]);
+        // Text which looks like a number is compared numerically
+        assert_eq!(matched("command > 40"), vec![2]);
+        // Displayed content is matched if the raw value isn't
+        assert_eq!(matched("command == pts/1"), vec![1]);
+        assert_eq!(matched("command == /dev/pts/1"), vec![1]);
+
+        // Missing value or column doesn't match any comparison including `!=`
+        assert_eq!(matched("user != nobody"), vec![1, 2]);
+        assert_eq!(matched("pid > 0"), Vec::<i32>::new());
+        assert_eq!(matched("pid != 0"), Vec::<i32>::new());
+        assert_eq!(matched("!(user == root)"), vec![2, 3]);
+
+        assert_eq!(matched("user == root || rss > 1G"), vec![1, 2]);
+        assert_eq!(matched("user == root && rss > 1G"), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_find_filter_kind() {
+        assert_eq!(find_filter_kind("pid"), Some(ConfigColumnKind::Pid));
+        assert_eq!(find_filter_kind("cpu"), Some(ConfigColumnKind::UsageCpu));
+        assert_eq!(find_filter_kind("mem"), Some(ConfigColumnKind::UsageMem));
+        assert_eq!(find_filter_kind("rss"), Some(ConfigColumnKind::VmRss));
+        assert_eq!(find_filter_kind("USER"), Some(ConfigColumnKind::User));
+        assert_eq!(find_filter_kind("tcp"), Some(ConfigColumnKind::TcpPort));
+        assert_eq!(find_filter_kind("pp"), Some(ConfigColumnKind::Ppid));
+        assert_eq!(find_filter_kind("pi"), Some(ConfigColumnKind::Pid));
+        assert_eq!(find_filter_kind("th"), Some(ConfigColumnKind::Threads));
+    }
+}
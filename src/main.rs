@@ -1,10 +1,22 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod capability;
 mod column;
 mod columns;
 mod config;
+mod diff;
+mod exporter;
+mod filter;
 mod opt;
 mod process;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod record;
 mod search_regex;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod signal;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod socket;
 mod style;
+mod template;
 mod term_info;
 mod util;
 mod view;
@@ -13,6 +25,7 @@ mod watcher;
 use crate::column::Column;
 use crate::columns::*;
 use crate::config::*;
+use crate::exporter::Exporter;
 use crate::opt::*;
 use crate::util::{adjust, get_theme, has_regex_syntax, lap};
 use crate::view::View;
@@ -143,6 +156,22 @@ fn run() -> Result<(), Error> {
     opt.watch_mode = opt.watch || opt.watch_interval.is_some();
     validate_search_args(&opt)?;
 
+    if !cfg!(any(target_os = "linux", target_os = "android"))
+        && (opt.record.is_some() || opt.replay.is_some())
+    {
+        anyhow::bail!("--record and --replay are supported on Linux only");
+    }
+
+    if !cfg!(any(target_os = "linux", target_os = "android")) && opt.diff_from.is_some() {
+        anyhow::bail!("--diff-from is supported on Linux only");
+    }
+
+    if opt.diff && !opt.watch_mode {
+        anyhow::bail!(
+            "--diff requires watch mode. Use --diff-from to compare with a recorded snapshot"
+        );
+    }
+
     if opt.gen_config {
         run_gen_config()
     } else if opt.list {
@@ -165,7 +194,9 @@ fn run() -> Result<(), Error> {
         Ok(())
     } else {
         let config = get_config(&opt)?;
-        if opt.watch_mode {
+        if let Some(addr) = &opt.serve {
+            Exporter::start(&opt, &config, addr)
+        } else if opt.watch_mode {
             let interval = match opt.watch_interval {
                 Some(n) => (n * 1000.0).round() as u64,
                 None => 1000,
@@ -310,6 +341,151 @@ mod tests {
         assert!(ret.is_ok());
     }
 
+    #[test]
+    fn test_run_filter() {
+        let mut config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
+        config.pager.mode = ConfigPagerMode::Disable;
+        config.display.theme = ConfigTheme::Dark;
+
+        let args = [
+            "procs",
+            "--filter",
+            "cpu >= 0 && (user == root || pid != 1)",
+        ];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        let args = [
+            "procs",
+            "--filter",
+            "ppid == 1 && vmrss < 1G && elapsed > 1s",
+        ];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        let args = [
+            "procs",
+            "--filter",
+            "command =~ \"^/usr\" && !(state == Z)",
+            "root",
+        ];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        let args = ["procs", "--filter", "cpu >"];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_err());
+    }
+
+    #[test]
+    fn test_run_search_qualified() {
+        let mut config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
+        config.pager.mode = ConfigPagerMode::Disable;
+        config.display.theme = ConfigTheme::Dark;
+
+        let args_list = [
+            vec!["procs", "user:root"],
+            vec!["procs", "--", "-user:root"],
+            vec!["procs", "!user:root"],
+            vec!["procs", "--or", "user:root", "ppid:1"],
+            vec!["procs", "--nand", "state:S", "root"],
+            vec!["procs", "--regex", "command:^/"],
+            vec!["procs", "--regex", "--", "-command:^/"],
+        ];
+        for args in args_list {
+            let mut opt = Opt::parse_from(args.iter());
+            let ret = run_default(&mut opt, &config);
+            assert!(ret.is_ok());
+        }
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_run_record_replay() {
+        let mut config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
+        config.pager.mode = ConfigPagerMode::Disable;
+        config.display.theme = ConfigTheme::Dark;
+
+        let path = std::env::temp_dir().join(format!("procs_record_replay_{}", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+
+        let args = ["procs", "--record", &path];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        let args = ["procs", "--replay", &path, "--tree", "--insert", "tcp"];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        let config: Config = toml::from_str(CONFIG_ALL).unwrap();
+        let args = ["procs", "--replay", &path, "--json", "root"];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_run_diff() {
+        let mut config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
+        config.pager.mode = ConfigPagerMode::Disable;
+        config.display.theme = ConfigTheme::Dark;
+
+        let path = std::env::temp_dir().join(format!("procs_diff_{}", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+
+        let args = ["procs", "--record", &path];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        let args = ["procs", "--diff-from", &path];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        let args = ["procs", "--diff-from", &path, "--replay", &path, "--json"];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_run_column_mode() {
+        let config = r#"
+[[columns]]
+kind = "Pid"
+[[columns]]
+kind = "TcpConn"
+mode = "List"
+nonnumeric_search = true
+"#;
+        let mut config: Config = toml::from_str(config).unwrap();
+        config.pager.mode = ConfigPagerMode::Disable;
+
+        let args = ["procs", "127.0.0.1"];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+
+        // Mode is rejected if the column doesn't support it
+        config.columns[0].mode = Some(ConfigColumnMode::List);
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_err());
+    }
+
     #[test]
     fn test_run_gen_config() {
         let ret = run_gen_config();
@@ -385,6 +561,21 @@ mod tests {
         assert!(ret.is_ok());
     }
 
+    #[test]
+    fn test_run_all_json() {
+        let mut config: Config = toml::from_str(CONFIG_ALL).unwrap();
+        config.pager.mode = ConfigPagerMode::Disable;
+        config.display.theme = ConfigTheme::Dark;
+
+        let _tcp = std::net::TcpListener::bind("127.0.0.1:10001");
+        let _udp = std::net::UdpSocket::bind("127.0.0.1:10001");
+
+        let args = ["procs", "--json-lines"];
+        let mut opt = Opt::parse_from(args.iter());
+        let ret = run_default(&mut opt, &config);
+        assert!(ret.is_ok());
+    }
+
     #[test]
     fn test_run_use_config() {
         let mut config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
@@ -29,6 +29,12 @@ pub enum ArgPagerMode {
     Disable,
 }
 
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArgFormat {
+    Csv,
+    Tsv,
+}
+
 #[derive(Clone, Debug, ValueEnum)]
 pub enum BuiltinConfig {
     Default,
@@ -97,6 +103,10 @@ pub struct Opt {
     )]
     pub nor: bool,
 
+    /// Filter expression ( ex. "cpu > 20 && user == root" )
+    #[clap(long = "filter", value_name = "expr")]
+    pub filter: Option<String>,
+
     /// Show list of kind
     #[clap(short = 'l', long = "list")]
     pub list: bool,
@@ -160,7 +170,46 @@ pub struct Opt {
     #[clap(long = "json")]
     pub json: bool,
 
-    /// Interval to calculate throughput
+    /// JSON Lines output ( one object per process, streamed in watch mode )
+    #[clap(long = "json-lines", conflicts_with_all(&["json", "format"]))]
+    pub json_lines: bool,
+
+    /// Delimiter-separated output
+    #[clap(long = "format", conflicts_with = "json")]
+    pub format: Option<ArgFormat>,
+
+    /// Output by template ( ex. "{pid}\t{user}\t{command}" ) or template name in config
+    #[clap(
+        long = "format-template",
+        value_name = "template",
+        conflicts_with_all(&["json", "json_lines", "format"])
+    )]
+    pub format_template: Option<String>,
+
+    /// Record collected processes to file ( appended at each update in watch mode )
+    #[clap(long = "record", value_name = "path", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replay processes recorded by --record ( advanced at each update in watch mode )
+    #[clap(long = "replay", value_name = "path")]
+    pub replay: Option<PathBuf>,
+
+    #[clap(skip)]
+    pub replay_index: usize,
+
+    /// Show processes changed from the previous update in watch mode
+    #[clap(long = "diff", conflicts_with = "diff_from")]
+    pub diff: bool,
+
+    /// Show processes changed from the first snapshot recorded by --record ( compare two recordings with --replay )
+    #[clap(long = "diff-from", value_name = "path")]
+    pub diff_from: Option<PathBuf>,
+
+    /// Serve metrics in Prometheus format on the address ( ex. 127.0.0.1:9256 )
+    #[clap(long = "serve", value_name = "addr")]
+    pub serve: Option<String>,
+
+    /// Interval to calculate throughput ( used at the first update only in watch mode on Linux )
     #[clap(long = "interval", default_value = "100", value_name = "millisec")]
     pub interval: u64,
 
@@ -15,3 +15,73 @@ pub use self::linux::*;
 pub use self::macos::*;
 #[cfg(target_os = "windows")]
 pub use self::windows::*;
+
+/// Optional data read from /proc. Each column declares what it requires.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DataSource {
+    pub io: bool,
+    pub status: bool,
+    /// `smaps_rollup` is expensive because the kernel walks all mappings
+    pub smaps: bool,
+    /// Count of `/proc/<pid>/fd` entries which is shared between columns
+    pub fd: bool,
+    /// Two samples separated by the interval are required to calculate rates
+    pub rate: bool,
+}
+
+impl DataSource {
+    pub const IO: DataSource = DataSource {
+        io: true,
+        status: false,
+        smaps: false,
+        fd: false,
+        rate: false,
+    };
+    pub const STATUS: DataSource = DataSource {
+        io: false,
+        status: true,
+        smaps: false,
+        fd: false,
+        rate: false,
+    };
+    pub const SMAPS: DataSource = DataSource {
+        io: false,
+        status: false,
+        smaps: true,
+        fd: false,
+        rate: false,
+    };
+    pub const FD: DataSource = DataSource {
+        io: false,
+        status: false,
+        smaps: false,
+        fd: true,
+        rate: false,
+    };
+    pub const RATE: DataSource = DataSource {
+        io: false,
+        status: false,
+        smaps: false,
+        fd: false,
+        rate: true,
+    };
+    /// Data recorded regardless of columns.
+    /// `smaps_rollup` and rates are recorded only if columns require them because they are expensive.
+    pub const RECORD: DataSource = DataSource {
+        io: true,
+        status: true,
+        smaps: false,
+        fd: true,
+        rate: false,
+    };
+
+    pub const fn merge(self, other: DataSource) -> DataSource {
+        DataSource {
+            io: self.io || other.io,
+            status: self.status || other.status,
+            smaps: self.smaps || other.smaps,
+            fd: self.fd || other.fd,
+            rate: self.rate || other.rate,
+        }
+    }
+}
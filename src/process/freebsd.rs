@@ -1,3 +1,4 @@
+use crate::process::DataSource;
 use bsd_kvm::{Access, KernProc, Kvm, Process};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -12,11 +13,15 @@ pub struct ProcessInfo {
     pub interval: Duration,
 }
 
+// Processes are always sampled twice because the columns don't declare their data sources on this platform
 pub fn collect_proc(
     interval: Duration,
     _with_thread: bool,
     _show_kthreads: bool,
     _procfs_path: &Option<PathBuf>,
+    _parallelism: usize,
+    _data_source: DataSource,
+    _reuse_sample: bool,
 ) -> Vec<ProcessInfo> {
     let mut base_procs = HashMap::new();
     let mut ret = Vec::new();
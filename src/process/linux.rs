@@ -1,8 +1,13 @@
+use crate::process::DataSource;
+use crate::socket::SocketTable;
 use procfs::ProcError;
 use procfs::ProcessCGroup;
-use procfs::process::{FDInfo, Io, Process, Stat, Status, TasksIter};
+use procfs::process::{FDTarget, Io, Limit, Process, SmapsRollup, Stat, Status, TasksIter};
+use serde_derive::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -16,6 +21,18 @@ pub enum ProcessTask {
         stat: Stat,
         owner: u32,
     },
+    Record {
+        stat: Stat,
+        owner: u32,
+        task: bool,
+        cmdline: Option<Vec<String>>,
+        cgroups: Option<Vec<ProcessCGroup>>,
+        sockets: Option<Vec<u64>>,
+        loginuid: Option<u32>,
+        wchan: Option<String>,
+        max_open_files: Option<Limit>,
+        socket_table: Option<Arc<SocketTable>>,
+    },
 }
 
 impl ProcessTask {
@@ -23,12 +40,16 @@ impl ProcessTask {
         match self {
             ProcessTask::Process { stat: x, .. } => x,
             ProcessTask::Task { stat: x, .. } => x,
+            ProcessTask::Record { stat: x, .. } => x,
         }
     }
 
     pub fn cmdline(&self) -> Result<Vec<String>, ProcError> {
         match self {
             ProcessTask::Process { proc: x, .. } => x.cmdline(),
+            ProcessTask::Record {
+                cmdline: Some(x), ..
+            } => Ok(x.clone()),
             _ => Err(ProcError::Other("not supported".to_string())),
         }
     }
@@ -36,13 +57,39 @@ impl ProcessTask {
     pub fn cgroups(&self) -> Result<Vec<ProcessCGroup>, ProcError> {
         match self {
             ProcessTask::Process { proc: x, .. } => x.cgroups().map(|x| x.0),
+            ProcessTask::Record {
+                cgroups: Some(x), ..
+            } => Ok(x.clone()),
             _ => Err(ProcError::Other("not supported".to_string())),
         }
     }
 
-    pub fn fd(&self) -> Result<Vec<FDInfo>, ProcError> {
+    /// Inodes of sockets opened by the process
+    pub fn sockets(&self) -> Result<Vec<u64>, ProcError> {
         match self {
-            ProcessTask::Process { proc: x, .. } => x.fd()?.collect(),
+            ProcessTask::Process { proc: x, .. } => Ok(x
+                .fd()?
+                .flatten()
+                .filter_map(|x| match x.target {
+                    FDTarget::Socket(x) => Some(x),
+                    _ => None,
+                })
+                .collect()),
+            ProcessTask::Record {
+                sockets: Some(x), ..
+            } => Ok(x.clone()),
+            _ => Err(ProcError::Other("not supported".to_string())),
+        }
+    }
+
+    /// Limit of the number of open files
+    pub fn max_open_files(&self) -> Result<Limit, ProcError> {
+        match self {
+            ProcessTask::Process { proc: x, .. } => x.limits().map(|x| x.max_open_files),
+            ProcessTask::Record {
+                max_open_files: Some(x),
+                ..
+            } => Ok(*x),
             _ => Err(ProcError::Other("not supported".to_string())),
         }
     }
@@ -50,6 +97,9 @@ impl ProcessTask {
     pub fn loginuid(&self) -> Result<u32, ProcError> {
         match self {
             ProcessTask::Process { proc: x, .. } => x.loginuid(),
+            ProcessTask::Record {
+                loginuid: Some(x), ..
+            } => Ok(*x),
             _ => Err(ProcError::Other("not supported".to_string())),
         }
     }
@@ -58,12 +108,36 @@ impl ProcessTask {
         match self {
             ProcessTask::Process { owner: x, .. } => *x,
             ProcessTask::Task { owner: x, .. } => *x,
+            ProcessTask::Record { owner: x, .. } => *x,
         }
     }
 
+    /// Socket table recorded with the process
+    pub fn socket_table(&self) -> Option<&SocketTable> {
+        match self {
+            ProcessTask::Record {
+                socket_table: Some(x),
+                ..
+            } => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn is_record(&self) -> bool {
+        matches!(self, ProcessTask::Record { .. })
+    }
+
+    pub fn is_task(&self) -> bool {
+        matches!(
+            self,
+            ProcessTask::Task { .. } | ProcessTask::Record { task: true, .. }
+        )
+    }
+
     pub fn wchan(&self) -> Result<String, ProcError> {
         match self {
             ProcessTask::Process { proc: x, .. } => x.wchan(),
+            ProcessTask::Record { wchan: Some(x), .. } => Ok(x.clone()),
             _ => Err(ProcError::Other("not supported".to_string())),
         }
     }
@@ -77,7 +151,159 @@ pub struct ProcessInfo {
     pub curr_io: Option<Io>,
     pub prev_io: Option<Io>,
     pub curr_status: Option<Status>,
+    pub curr_smaps: Option<Smaps>,
+    pub curr_fd_count: Option<usize>,
+    pub interval: Duration,
+}
+
+#[cfg(test)]
+impl ProcessInfo {
+    /// Process of `pid` which reads this process, for tests of columns.
+    /// Other fields can be given by struct update syntax.
+    pub fn for_test(pid: i32) -> Self {
+        let proc = Process::myself().unwrap();
+        ProcessInfo {
+            pid,
+            ppid: 0,
+            curr_proc: ProcessTask::Process {
+                stat: proc.stat().unwrap(),
+                owner: proc.uid().unwrap(),
+                proc,
+            },
+            prev_stat: Process::myself().unwrap().stat().unwrap(),
+            curr_io: None,
+            prev_io: None,
+            curr_status: None,
+            curr_smaps: None,
+            curr_fd_count: None,
+            interval: Duration::new(0, 0),
+        }
+    }
+}
+
+/// Fields of `smaps_rollup` in bytes like `Pss` or `Private_Clean`
+pub type Smaps = HashMap<String, u64>;
+
+/// Serializable form of ProcessInfo for recording.
+/// Only the data used by columns is kept, and unavailable data is omitted.
+#[derive(Serialize, Deserialize)]
+pub struct ProcessRecord {
+    pub pid: i32,
+    pub ppid: i32,
+    pub task: bool,
+    pub owner: u32,
+    pub stat: Stat,
+    pub prev_stat: Stat,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub io: Option<Io>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_io: Option<Io>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smaps: Option<Smaps>,
     pub interval: Duration,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cmdline: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroups: Option<Vec<ProcessCGroup>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fd_count: Option<usize>,
+    /// Inodes of sockets instead of all file descriptors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sockets: Option<Vec<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loginuid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wchan: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_open_files: Option<Limit>,
+    /// Inode of the network namespace to find the socket table in the snapshot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub netns: Option<u64>,
+    /// Socket table of `netns` which is set when the snapshot is loaded
+    #[serde(skip)]
+    pub socket_table: Option<Arc<SocketTable>>,
+}
+
+impl From<&ProcessInfo> for ProcessRecord {
+    fn from(x: &ProcessInfo) -> Self {
+        ProcessRecord {
+            pid: x.pid,
+            ppid: x.ppid,
+            task: x.curr_proc.is_task(),
+            owner: x.curr_proc.owner(),
+            stat: x.curr_proc.stat().clone(),
+            prev_stat: x.prev_stat.clone(),
+            io: x.curr_io,
+            prev_io: x.prev_io,
+            status: x.curr_status.clone(),
+            smaps: x.curr_smaps.clone(),
+            interval: x.interval,
+            cmdline: x.curr_proc.cmdline().ok(),
+            cgroups: x.curr_proc.cgroups().ok(),
+            fd_count: x.curr_fd_count,
+            sockets: x.curr_proc.sockets().ok(),
+            loginuid: x.curr_proc.loginuid().ok(),
+            wchan: x.curr_proc.wchan().ok(),
+            max_open_files: x.curr_proc.max_open_files().ok(),
+            netns: None,
+            socket_table: None,
+        }
+    }
+}
+
+impl From<ProcessRecord> for ProcessInfo {
+    fn from(x: ProcessRecord) -> Self {
+        // Threads are kept as records too, so that columns don't read a live thread which has the same id
+        let curr_proc = ProcessTask::Record {
+            stat: x.stat,
+            owner: x.owner,
+            task: x.task,
+            cmdline: x.cmdline,
+            cgroups: x.cgroups,
+            sockets: x.sockets,
+            loginuid: x.loginuid,
+            wchan: x.wchan,
+            max_open_files: x.max_open_files,
+            socket_table: x.socket_table,
+        };
+        ProcessInfo {
+            pid: x.pid,
+            ppid: x.ppid,
+            curr_proc,
+            prev_stat: x.prev_stat,
+            curr_io: x.io,
+            prev_io: x.prev_io,
+            curr_status: x.status,
+            curr_smaps: x.smaps,
+            curr_fd_count: x.fd_count,
+            interval: x.interval,
+        }
+    }
+}
+
+type TaskMap = HashMap<i32, (i32, Stat, Option<Status>, Option<Io>)>;
+
+// Sampled stat, io, time and tasks of each process
+type Sample = HashMap<i32, (Stat, Option<Io>, Instant, TaskMap)>;
+
+thread_local! {
+    // Sample of the last collection. It is used as the baseline of the next collection in watch mode.
+    static LAST_SAMPLE: RefCell<Option<Sample>> = const { RefCell::new(None) };
+}
+
+struct CurrentSample {
+    pid: i32,
+    proc: Process,
+    stat: Stat,
+    owner: u32,
+    io: Option<Io>,
+    status: Option<Status>,
+    smaps: Option<Smaps>,
+    fd_count: Option<usize>,
+    time: Instant,
+    tasks: TaskMap,
 }
 
 pub fn collect_proc(
@@ -85,110 +311,242 @@ pub fn collect_proc(
     with_thread: bool,
     show_kthreads: bool,
     procfs_path: &Option<PathBuf>,
+    parallelism: usize,
+    data_source: DataSource,
+    reuse_sample: bool,
 ) -> Vec<ProcessInfo> {
-    let mut base_procs = Vec::new();
-    let mut base_tasks = HashMap::new();
-    let mut ret = Vec::new();
-
-    let all_proc = if let Some(x) = procfs_path {
-        procfs::process::all_processes_with_root(x)
+    // Two samples are required to calculate rates only.
+    // The last sample is reused as the baseline instead of sampling and sleeping again if available.
+    let mut base_procs = if !data_source.rate {
+        None
+    } else if let Some(x) = reuse_sample.then(|| LAST_SAMPLE.take()).flatten() {
+        Some(x)
     } else {
-        procfs::process::all_processes()
-    };
-
-    if let Ok(all_proc) = all_proc {
-        for proc in all_proc.flatten() {
-            if let Ok(stat) = proc.stat() {
-                let io = proc.io().ok();
-                let time = Instant::now();
-                if with_thread && let Ok(iter) = proc.tasks() {
-                    collect_task(iter, &mut base_tasks);
-                }
-                base_procs.push((proc.pid(), stat, io, time));
+        let base_procs: Sample = parallel_map(all_processes(procfs_path), parallelism, |proc| {
+            let stat = proc.stat().ok()?;
+            let io = data_source.io.then(|| proc.io().ok()).flatten();
+            let time = Instant::now();
+            let mut base_tasks = HashMap::new();
+            if with_thread && let Ok(iter) = proc.tasks() {
+                collect_task(iter, &mut base_tasks, data_source);
             }
-        }
-    }
+            Some((proc.pid(), (stat, io, time, base_tasks)))
+        })
+        .into_iter()
+        .collect();
 
-    thread::sleep(interval);
+        thread::sleep(interval);
+        Some(base_procs)
+    };
 
-    for (pid, prev_stat, prev_io, prev_time) in base_procs {
-        let curr_proc = if let Ok(proc) = crate::util::process_new(pid, procfs_path) {
-            proc
-        } else {
-            continue;
-        };
+    let curr_procs = parallel_map(all_processes(procfs_path), parallelism, |proc| {
+        collect_curr(proc, procfs_path, with_thread, show_kthreads, data_source)
+    });
 
-        let curr_stat = if let Ok(stat) = curr_proc.stat() {
-            stat
-        } else {
-            continue;
-        };
+    let sampled = base_procs.is_some();
+    let mut last_sample = HashMap::new();
+    let mut ret = Vec::new();
+    for curr in curr_procs {
+        let pid = curr.pid;
+        if reuse_sample && data_source.rate {
+            last_sample.insert(
+                pid,
+                (curr.stat.clone(), curr.io, curr.time, curr.tasks.clone()),
+            );
+        }
 
-        let curr_owner = if let Ok(owner) = curr_proc.uid() {
-            owner
+        // Without the first sample, the current sample is the baseline because rates are not shown.
+        // Otherwise a process which has no sample in the first pass is skipped.
+        let (prev_stat, prev_io, interval, mut prev_tasks) = if !sampled {
+            (curr.stat.clone(), curr.io, interval, HashMap::new())
+        } else if let Some((prev_stat, prev_io, prev_time, prev_tasks)) =
+            take_baseline(&mut base_procs, pid, &curr.stat)
+        {
+            (prev_stat, prev_io, curr.time - prev_time, prev_tasks)
         } else {
             continue;
         };
+        let ppid = curr.stat.ppid;
 
-        let curr_io = curr_proc.io().ok();
-        let curr_status = curr_proc.status().ok();
-        let curr_time = Instant::now();
-        let interval = curr_time - prev_time;
-        let ppid = curr_stat.ppid;
-
-        if !show_kthreads && (ppid == 2 || pid == 2) {
-            continue;
-        }
-
-        let mut curr_tasks = HashMap::new();
-        if with_thread && let Ok(iter) = curr_proc.tasks() {
-            collect_task(iter, &mut curr_tasks);
-        }
-
-        let curr_proc = ProcessTask::Process {
-            stat: curr_stat,
-            owner: curr_owner,
-            proc: curr_proc,
-        };
-
-        let proc = ProcessInfo {
+        ret.push(ProcessInfo {
             pid,
             ppid,
-            curr_proc,
+            curr_proc: ProcessTask::Process {
+                stat: curr.stat,
+                owner: curr.owner,
+                proc: curr.proc,
+            },
             prev_stat,
-            curr_io,
+            curr_io: curr.io,
             prev_io,
-            curr_status,
+            curr_status: curr.status,
+            curr_smaps: curr.smaps,
+            curr_fd_count: curr.fd_count,
             interval,
-        };
+        });
 
-        ret.push(proc);
-
-        for (tid, (pid, curr_stat, curr_status, curr_io)) in curr_tasks {
-            if let Some((_, prev_stat, _, prev_io)) = base_tasks.remove(&tid) {
-                let proc = ProcessInfo {
-                    pid: tid,
-                    ppid: pid,
-                    curr_proc: ProcessTask::Task {
-                        stat: curr_stat,
-                        owner: curr_owner,
-                    },
-                    prev_stat,
-                    curr_io,
-                    prev_io,
-                    curr_status,
-                    interval,
-                };
-                ret.push(proc);
-            }
+        for (tid, (pid, curr_stat, curr_status, curr_io)) in curr.tasks {
+            let (prev_stat, prev_io) = if !sampled {
+                (curr_stat.clone(), curr_io)
+            } else if let Some((_, prev_stat, _, prev_io)) = prev_tasks
+                .remove(&tid)
+                .filter(|(_, prev_stat, _, _)| prev_stat.starttime == curr_stat.starttime)
+            {
+                (prev_stat, prev_io)
+            } else {
+                continue;
+            };
+            ret.push(ProcessInfo {
+                pid: tid,
+                ppid: pid,
+                curr_proc: ProcessTask::Task {
+                    stat: curr_stat,
+                    owner: curr.owner,
+                },
+                prev_stat,
+                curr_io,
+                prev_io,
+                curr_status,
+                curr_smaps: None,
+                curr_fd_count: None,
+                interval,
+            });
         }
     }
 
+    if reuse_sample && data_source.rate {
+        LAST_SAMPLE.set(Some(last_sample));
+    }
+
     ret
 }
 
-#[allow(clippy::type_complexity)]
-fn collect_task(iter: TasksIter, map: &mut HashMap<i32, (i32, Stat, Option<Status>, Option<Io>)>) {
+// Baseline of a process in the sample.
+// It is discarded if the PID has been reused by another process since the sample was taken.
+fn take_baseline(
+    base_procs: &mut Option<Sample>,
+    pid: i32,
+    stat: &Stat,
+) -> Option<(Stat, Option<Io>, Instant, TaskMap)> {
+    base_procs
+        .as_mut()?
+        .remove(&pid)
+        .filter(|(prev_stat, _, _, _)| prev_stat.starttime == stat.starttime)
+}
+
+// `smaps_rollup` has a single entry which sums up all mappings
+fn smaps_from_rollup(rollup: SmapsRollup) -> Option<Smaps> {
+    rollup
+        .memory_map_rollup
+        .0
+        .into_iter()
+        .next()
+        .map(|x| x.extension.map)
+}
+
+fn all_processes(procfs_path: &Option<PathBuf>) -> Vec<Process> {
+    let all_proc = if let Some(x) = procfs_path {
+        procfs::process::all_processes_with_root(x)
+    } else {
+        procfs::process::all_processes()
+    };
+
+    if let Ok(all_proc) = all_proc {
+        all_proc.flatten().collect()
+    } else {
+        Vec::new()
+    }
+}
+
+// Collect the current state of a process and its tasks
+fn collect_curr(
+    proc: Process,
+    procfs_path: &Option<PathBuf>,
+    with_thread: bool,
+    show_kthreads: bool,
+    data_source: DataSource,
+) -> Option<CurrentSample> {
+    let pid = proc.pid();
+    let stat = proc.stat().ok()?;
+    let owner = proc.uid().ok()?;
+
+    if !show_kthreads && (stat.ppid == 2 || pid == 2) {
+        return None;
+    }
+
+    let io = data_source.io.then(|| proc.io().ok()).flatten();
+    let status = data_source.status.then(|| proc.status().ok()).flatten();
+    let smaps = data_source
+        .smaps
+        .then(|| proc.smaps_rollup().ok())
+        .flatten()
+        .and_then(smaps_from_rollup);
+    // Entries are counted without reading the links because only the number is required.
+    // read_dir doesn't yield `.` and `..` unlike Process::fd_count without the fast path.
+    let fd_count = data_source
+        .fd
+        .then(|| {
+            std::fs::read_dir(crate::util::procfs_file(procfs_path, &format!("{pid}/fd"))).ok()
+        })
+        .flatten()
+        .map(|x| x.count());
+    let time = Instant::now();
+
+    let mut tasks = HashMap::new();
+    if with_thread && let Ok(iter) = proc.tasks() {
+        collect_task(iter, &mut tasks, data_source);
+    }
+
+    Some(CurrentSample {
+        pid,
+        proc,
+        stat,
+        owner,
+        io,
+        status,
+        smaps,
+        fd_count,
+        time,
+        tasks,
+    })
+}
+
+// Apply `f` to `items` by `parallelism` workers. The order of `items` is kept.
+fn parallel_map<T, U, F>(items: Vec<T>, parallelism: usize, f: F) -> Vec<U>
+where
+    T: Send,
+    U: Send,
+    F: Fn(T) -> Option<U> + Sync,
+{
+    if parallelism <= 1 || items.len() <= 1 {
+        return items.into_iter().filter_map(f).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(parallelism);
+    let mut chunks = Vec::new();
+    let mut items = items.into_iter();
+    loop {
+        let chunk: Vec<_> = items.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push(chunk);
+    }
+
+    let f = &f;
+    thread::scope(|s| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| s.spawn(move || chunk.into_iter().filter_map(f).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|x| x.join().unwrap())
+            .collect()
+    })
+}
+
+fn collect_task(iter: TasksIter, map: &mut TaskMap, data_source: DataSource) {
     for task in iter {
         let task = if let Ok(x) = task {
             x
@@ -201,9 +559,141 @@ fn collect_task(iter: TasksIter, map: &mut HashMap<i32, (i32, Stat, Option<Statu
             } else {
                 continue;
             };
-            let status = task.status().ok();
-            let io = task.io().ok();
+            let status = data_source.status.then(|| task.status().ok()).flatten();
+            let io = data_source.io.then(|| task.io().ok()).flatten();
             map.insert(task.tid, (task.pid, stat, status, io));
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use procfs::FromBufRead;
+
+    #[test]
+    fn test_parallel_map() {
+        let items: Vec<i32> = (0..1000).collect();
+        let expected: Vec<i32> = items
+            .iter()
+            .filter(|x| *x % 3 != 0)
+            .map(|x| x * 2)
+            .collect();
+        for parallelism in [0, 1, 4, 7, 2000] {
+            let ret = parallel_map(items.clone(), parallelism, |x| {
+                (x % 3 != 0).then_some(x * 2)
+            });
+            assert_eq!(ret, expected);
+        }
+    }
+
+    #[test]
+    fn test_take_baseline() {
+        let stat = Process::myself().unwrap().stat().unwrap();
+        let pid = stat.pid;
+        let sample = |starttime| {
+            let mut prev_stat = stat.clone();
+            prev_stat.starttime = starttime;
+            let mut sample = Sample::new();
+            sample.insert(pid, (prev_stat, None, Instant::now(), HashMap::new()));
+            Some(sample)
+        };
+
+        let mut base_procs = sample(stat.starttime);
+        assert!(take_baseline(&mut base_procs, pid, &stat).is_some());
+        assert!(take_baseline(&mut base_procs, pid, &stat).is_none());
+
+        // The PID is reused by another process
+        let mut base_procs = sample(stat.starttime + 100);
+        assert!(take_baseline(&mut base_procs, pid, &stat).is_none());
+
+        assert!(take_baseline(&mut None, pid, &stat).is_none());
+    }
+
+    #[test]
+    fn test_collect_proc_started_process() {
+        // A child process starts during the interval of collect_proc
+        let spawner = thread::spawn(|| {
+            thread::sleep(Duration::from_millis(200));
+            std::process::Command::new("sleep")
+                .arg("2")
+                .spawn()
+                .unwrap()
+        });
+        let interval = Duration::from_millis(1000);
+        let procs = collect_proc(interval, false, false, &None, 1, DataSource::RATE, false);
+        let mut child = spawner.join().unwrap();
+
+        let proc = procs.iter().find(|x| x.pid == child.id() as i32);
+        let _ = child.kill();
+        let _ = child.wait();
+
+        // The rates of the child can't be calculated because it has no sample in the first pass
+        assert!(proc.is_none());
+        assert!(procs.iter().any(|x| x.pid == std::process::id() as i32));
+    }
+
+    #[test]
+    fn test_smaps_from_rollup() {
+        let text = "\
+55d1c2a5e000-7ffc8a7f5000 ---p 00000000 00:00 0                          [rollup]
+Rss:                4096 kB
+Pss:                1234 kB
+Pss_Anon:            512 kB
+Shared_Clean:       2048 kB
+Shared_Dirty:          0 kB
+Private_Clean:      1024 kB
+Private_Dirty:      1024 kB
+Swap:                  0 kB
+SwapPss:               8 kB
+";
+        let rollup = SmapsRollup::from_buf_read(text.as_bytes()).unwrap();
+        let smaps = smaps_from_rollup(rollup).unwrap();
+        assert_eq!(smaps["Rss"], 4096 * 1024);
+        assert_eq!(smaps["Pss"], 1234 * 1024);
+        assert_eq!(smaps["Shared_Clean"], 2048 * 1024);
+        assert_eq!(smaps["Private_Clean"], 1024 * 1024);
+        assert_eq!(smaps["SwapPss"], 8 * 1024);
+
+        let rollup = SmapsRollup::from_buf_read("".as_bytes()).unwrap();
+        assert!(smaps_from_rollup(rollup).is_none());
+    }
+
+    #[test]
+    fn test_record_task() {
+        let proc = Process::myself().unwrap();
+        let record = |task| ProcessRecord {
+            pid: proc.pid,
+            ppid: 0,
+            task,
+            owner: 0,
+            stat: proc.stat().unwrap(),
+            prev_stat: proc.stat().unwrap(),
+            io: None,
+            prev_io: None,
+            status: None,
+            smaps: None,
+            interval: Duration::new(0, 0),
+            cmdline: None,
+            cgroups: None,
+            fd_count: None,
+            sockets: None,
+            loginuid: None,
+            wchan: None,
+            max_open_files: None,
+            netns: None,
+            socket_table: None,
+        };
+
+        // Recorded threads don't fall back to the live /proc
+        let info = ProcessInfo::from(record(true));
+        assert!(info.curr_proc.is_record());
+        assert!(info.curr_proc.is_task());
+        assert!(info.curr_proc.cmdline().is_err());
+        assert!(ProcessRecord::from(&info).task);
+
+        let info = ProcessInfo::from(record(false));
+        assert!(info.curr_proc.is_record());
+        assert!(!info.curr_proc.is_task());
+    }
+}
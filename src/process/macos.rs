@@ -1,3 +1,4 @@
+use crate::process::DataSource;
 use libc::{c_int, c_void, size_t};
 use libproc::libproc::bsd_info::BSDInfo;
 use libproc::libproc::file_info::{ListFDs, ProcFDType, pidfdinfo};
@@ -27,11 +28,15 @@ pub struct ProcessInfo {
     pub interval: Duration,
 }
 
+// Processes are always sampled twice because the columns don't declare their data sources on this platform
 pub fn collect_proc(
     interval: Duration,
     _with_thread: bool,
     _show_kthreads: bool,
     _procfs_path: &Option<PathBuf>,
+    _parallelism: usize,
+    _data_source: DataSource,
+    _reuse_sample: bool,
 ) -> Vec<ProcessInfo> {
     let mut base_procs = Vec::new();
     let mut ret = Vec::new();
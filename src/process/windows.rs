@@ -1,3 +1,4 @@
+use crate::process::DataSource;
 use chrono::offset::TimeZone;
 use chrono::{Local, NaiveDate};
 use std::cell::RefCell;
@@ -71,11 +72,15 @@ pub struct CpuInfo {
     pub curr_user: u64,
 }
 
+// Processes are always sampled twice because the columns don't declare their data sources on this platform
 pub fn collect_proc(
     interval: Duration,
     _with_thread: bool,
     _show_kthreads: bool,
     _procfs_path: &Option<PathBuf>,
+    _parallelism: usize,
+    _data_source: DataSource,
+    _reuse_sample: bool,
 ) -> Vec<ProcessInfo> {
     let mut base_procs = Vec::new();
     let mut ret = Vec::new();
@@ -0,0 +1,224 @@
+use crate::process::{ProcessInfo, ProcessRecord};
+use crate::socket::{SocketIndex, SocketTable};
+use crate::util::SystemState;
+use anyhow::{Context, Error, bail};
+use chrono::Local;
+use flate2::Compression;
+use flate2::bufread::GzDecoder;
+use flate2::write::GzEncoder;
+use serde_derive::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Processes collected at a time. A record file has a snapshot per line.
+/// If the file name ends with `.gz`, each snapshot is compressed as a gzip member.
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    timestamp: String,
+    system: SystemState,
+    processes: Vec<ProcessRecord>,
+    /// Sockets of the processes in each network namespace keyed by the namespace inode
+    #[serde(default)]
+    sockets: HashMap<u64, SocketTable>,
+}
+
+pub fn save(
+    path: &Path,
+    procs: &[ProcessInfo],
+    system: SystemState,
+    procfs: &Option<PathBuf>,
+) -> Result<(), Error> {
+    let index = SocketIndex::shared(procfs);
+    let mut index = index.borrow_mut();
+    let mut sockets: HashMap<u64, SocketTable> = HashMap::new();
+    let mut processes = Vec::new();
+    for proc in procs {
+        let mut record = ProcessRecord::from(proc);
+        if let Some(inodes) = record.sockets.as_ref().filter(|x| !x.is_empty())
+            && let Some(netns) = crate::util::ns_inode(proc.pid, procfs, "net")
+            && let Some(table) = index.table(proc)
+        {
+            sockets.entry(netns).or_default().extend_from(table, inodes);
+            record.netns = Some(netns);
+        }
+        processes.push(record);
+    }
+
+    let snapshot = Snapshot {
+        timestamp: Local::now().to_rfc3339(),
+        system,
+        processes,
+        sockets,
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context(format!("failed to open file ({path:?})"))?;
+    let mut line = serde_json::to_string(&snapshot)?;
+    line.push('\n');
+    if is_gzip(path) {
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        encoder.write_all(line.as_bytes())?;
+        encoder.finish()?;
+    } else {
+        file.write_all(line.as_bytes())?;
+    }
+    Ok(())
+}
+
+fn is_gzip(path: &Path) -> bool {
+    path.extension().is_some_and(|x| x == "gz")
+}
+
+/// Read the snapshot at the current position, or `None` at the end of the file
+fn read_next(reader: &mut BufReader<File>, gzip: bool) -> Result<Option<String>, Error> {
+    let mut line = String::new();
+    if gzip {
+        if reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        // The decoder consumes one gzip member only, so the reader is at the next snapshot
+        GzDecoder::new(&mut *reader).read_to_string(&mut line)?;
+    } else if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line))
+}
+
+thread_local! {
+    // Offsets of the snapshots found in each record file.
+    // Replay reads the next snapshot at each update, so the file is not scanned from the start again.
+    static OFFSETS: RefCell<HashMap<PathBuf, Vec<u64>>> = RefCell::new(HashMap::new());
+}
+
+/// Read the line of the snapshot at `index`, or the last snapshot if `index` exceeds the number of snapshots.
+fn read_snapshot(path: &Path, index: usize) -> Result<Option<String>, Error> {
+    let file = File::open(path).context(format!("failed to open file ({path:?})"))?;
+    let mut reader = BufReader::new(file);
+    let gzip = is_gzip(path);
+
+    OFFSETS.with_borrow_mut(|offsets| {
+        let offsets = offsets.entry(path.to_path_buf()).or_insert_with(|| vec![0]);
+
+        // Resume from the nearest known snapshot.
+        // The last offset is the end of the known snapshots, so the snapshot before it is read again.
+        let mut i = index.min(offsets.len().saturating_sub(2));
+        let mut offset = offsets[i];
+        reader.seek(SeekFrom::Start(offset))?;
+
+        let mut ret = None;
+        loop {
+            let Some(line) = read_next(&mut reader, gzip)? else {
+                break;
+            };
+            offset = reader.stream_position()?;
+            if i + 1 == offsets.len() {
+                offsets.push(offset);
+            }
+            ret = Some(line);
+            if i == index {
+                break;
+            }
+            i += 1;
+        }
+
+        Ok(ret)
+    })
+}
+
+/// Load the snapshot at `index` and the system state when it is recorded.
+/// The last snapshot is used if `index` exceeds the number of snapshots.
+pub fn load(
+    path: &Path,
+    index: usize,
+    with_thread: bool,
+    show_kthreads: bool,
+) -> Result<(Vec<ProcessInfo>, SystemState), Error> {
+    let Some(line) = read_snapshot(path, index)? else {
+        bail!("no snapshot is recorded in {:?}", path);
+    };
+
+    let snapshot: Snapshot =
+        serde_json::from_str(&line).context(format!("failed to parse snapshot ({path:?})"))?;
+
+    let sockets: HashMap<_, _> = snapshot
+        .sockets
+        .into_iter()
+        .map(|(netns, table)| (netns, Arc::new(table)))
+        .collect();
+    let procs = snapshot
+        .processes
+        .into_iter()
+        .filter(|x| with_thread || !x.task)
+        .filter(|x| show_kthreads || !(x.ppid == 2 || x.pid == 2))
+        .map(|mut x| {
+            x.socket_table = x.netns.and_then(|netns| sockets.get(&netns).cloned());
+            ProcessInfo::from(x)
+        })
+        .collect();
+    Ok((procs, snapshot.system))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_snapshot() {
+        let path =
+            std::env::temp_dir().join(format!("procs_record_snapshot_{}", std::process::id()));
+        std::fs::write(&path, "a\nbb\nccc\n").unwrap();
+
+        let read = |index| read_snapshot(&path, index).unwrap().unwrap();
+        assert_eq!(read(0), "a\n");
+        assert_eq!(read(2), "ccc\n");
+        assert_eq!(read(1), "bb\n");
+        assert_eq!(read(5), "ccc\n");
+
+        // Snapshots appended while replaying are found
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"dddd\n").unwrap();
+        assert_eq!(read(3), "dddd\n");
+        assert_eq!(read(0), "a\n");
+
+        let _ = std::fs::remove_file(&path);
+        std::fs::write(&path, "").unwrap();
+        OFFSETS.with_borrow_mut(|x| x.clear());
+        assert!(read_snapshot(&path, 0).unwrap().is_none());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_read_snapshot_gzip() {
+        let path =
+            std::env::temp_dir().join(format!("procs_record_snapshot_{}.gz", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let append = |line: &str| {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .unwrap();
+            let mut encoder = GzEncoder::new(file, Compression::default());
+            encoder.write_all(line.as_bytes()).unwrap();
+            encoder.finish().unwrap();
+        };
+        append("a\n");
+        append("bb\n");
+
+        let read = |index| read_snapshot(&path, index).unwrap().unwrap();
+        assert_eq!(read(1), "bb\n");
+        assert_eq!(read(0), "a\n");
+        assert_eq!(read(5), "bb\n");
+
+        append("ccc\n");
+        assert_eq!(read(2), "ccc\n");
+        let _ = std::fs::remove_file(&path);
+    }
+}
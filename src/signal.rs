@@ -0,0 +1,71 @@
+// Indexed by signal number - 1. See signal(7)
+const NAMES: [&str; 31] = [
+    "HUP", "INT", "QUIT", "ILL", "TRAP", "ABRT", "BUS", "FPE", "KILL", "USR1", "SEGV", "USR2",
+    "PIPE", "ALRM", "TERM", "STKFLT", "CHLD", "CONT", "STOP", "TSTP", "TTIN", "TTOU", "URG",
+    "XCPU", "XFSZ", "VTALRM", "PROF", "WINCH", "IO", "PWR", "SYS",
+];
+
+/// Names of signals in `mask` like `HUP`.
+/// Real-time signals are named from SIGRTMIN of glibc like `RTMIN+1` or `RTMAX-2` as `kill -l` does.
+/// Signal 32 and 33 which glibc reserves are shown by number.
+pub fn names(mask: u64) -> Vec<String> {
+    (0..64)
+        .filter(|x| mask & (1 << x) != 0)
+        .map(|x| {
+            let num = x + 1;
+            match num {
+                1..=31 => NAMES[x].to_string(),
+                34 => String::from("RTMIN"),
+                35..=49 => format!("RTMIN+{}", num - 34),
+                50..=63 => format!("RTMAX-{}", 64 - num),
+                64 => String::from("RTMAX"),
+                _ => format!("{num}"),
+            }
+        })
+        .collect()
+}
+
+/// Whether `names` contains `keyword` like `HUP` or `SIGHUP` ignoring case.
+/// Numeric keywords are not signal names, so they never match.
+pub fn contains(names: &[String], keyword: &str) -> bool {
+    if keyword.chars().all(|x| x.is_ascii_digit()) {
+        return false;
+    }
+    let keyword = keyword.to_ascii_uppercase();
+    let keyword = keyword.strip_prefix("SIG").unwrap_or(&keyword);
+    names.iter().any(|x| x == keyword)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(names(0), Vec::<String>::new());
+        assert_eq!(names(0x4003), vec!["HUP", "INT", "TERM"]);
+        assert_eq!(names(1 << 31), vec!["32"]);
+        assert_eq!(names(1 << 33), vec!["RTMIN"]);
+        assert_eq!(names(1 << 34), vec!["RTMIN+1"]);
+        assert_eq!(names(1 << 48), vec!["RTMIN+15"]);
+        assert_eq!(names(1 << 49), vec!["RTMAX-14"]);
+        assert_eq!(names(1 << 63), vec!["RTMAX"]);
+    }
+
+    #[test]
+    fn test_contains() {
+        let x = names(0x4001);
+        assert!(contains(&x, "HUP"));
+        assert!(contains(&x, "sighup"));
+        assert!(contains(&x, "term"));
+        assert!(!contains(&x, "INT"));
+        assert!(!contains(&x, "SIG"));
+
+        let x = names(0x3 << 30);
+        assert!(!contains(&x, "32"));
+        let x = names(1 << 34);
+        assert!(contains(&x, "SIGRTMIN+1"));
+        assert!(contains(&x, "rtmin+1"));
+        assert!(!contains(&x, "35"));
+    }
+}
@@ -0,0 +1,332 @@
+use crate::process::ProcessInfo;
+use procfs::FromRead;
+use procfs::FromReadSI;
+use procfs::ProcResult;
+use procfs::net::{
+    TcpNetEntries, TcpNetEntry, UdpNetEntries, UdpNetEntry, UnixNetEntries, UnixNetEntry,
+};
+use serde_derive::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::BufRead;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::rc::{Rc, Weak};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Socket {
+    Tcp(TcpNetEntry),
+    Udp(UdpNetEntry),
+    Unix(UnixNetEntry),
+}
+
+/// Local address of a bound socket like `tcp4 0.0.0.0:8080`
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BoundAddr {
+    pub tcp: bool,
+    pub addr: SocketAddr,
+}
+
+impl BoundAddr {
+    /// Whether the socket is reachable from other hosts, that is bound to other than loopback
+    pub fn is_public(&self) -> bool {
+        !self.addr.ip().to_canonical().is_loopback()
+    }
+
+    /// Match `public`, port, address or the whole like `tcp4 0.0.0.0:8080`
+    pub fn matches(&self, keyword: &str) -> bool {
+        (keyword == "public" && self.is_public())
+            || self.addr.port().to_string() == keyword
+            || self.addr.to_string() == keyword
+            || self.to_string() == keyword
+    }
+}
+
+impl fmt::Display for BoundAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let proto = if self.tcp { "tcp" } else { "udp" };
+        let version = if self.addr.is_ipv4() { 4 } else { 6 };
+        write!(f, "{proto}{version} {}", self.addr)
+    }
+}
+
+/// `__SO_ACCEPTCON` in the flags of `/proc/net/unix` which is set by `listen(2)`
+const SO_ACCEPTCON: u32 = 0x10000;
+
+/// Inodes of listening stream and seqpacket sockets in `/proc/net/unix`.
+/// `UnixNetEntry` doesn't have the flags, so they are parsed here.
+fn unix_listening<R: BufRead>(r: R) -> HashSet<u64> {
+    let mut ret = HashSet::new();
+    for line in r.lines().skip(1).map_while(Result::ok) {
+        // Num RefCount Protocol Flags Type St Inode Path
+        let fields: Vec<_> = line.split_whitespace().collect();
+        if fields.len() < 7 {
+            continue;
+        }
+        let flags = u32::from_str_radix(fields[3], 16);
+        let socket_type = i32::from_str_radix(fields[4], 16);
+        let inode = fields[6].parse::<u64>();
+        if let (Ok(flags), Ok(socket_type), Ok(inode)) = (flags, socket_type, inode)
+            && flags & SO_ACCEPTCON != 0
+            && (socket_type == libc::SOCK_STREAM || socket_type == libc::SOCK_SEQPACKET)
+        {
+            ret.insert(inode);
+        }
+    }
+    ret
+}
+
+/// Sockets in a network namespace keyed by inode
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SocketTable {
+    sockets: HashMap<u64, Socket>,
+    unix_listening: HashSet<u64>,
+}
+
+impl SocketTable {
+    /// Read `tcp`, `tcp6`, `udp`, `udp6` and `unix` in `dir` like `net` or `<pid>/net`
+    pub fn read(procfs: &Option<PathBuf>, dir: &str) -> ProcResult<Self> {
+        let si = &crate::util::procfs_system_info(procfs);
+        let path = |name: &str| crate::util::procfs_file(procfs, &format!("{dir}/{name}"));
+
+        let mut sockets = HashMap::new();
+        let tcp = TcpNetEntries::from_file(path("tcp"), si)?.0;
+        // tcp6, udp and udp6 may not exist if they are disabled
+        let tcp6 = TcpNetEntries::from_file(path("tcp6"), si).map_or(Vec::new(), |x| x.0);
+        for x in tcp.into_iter().chain(tcp6) {
+            if x.inode != 0 {
+                sockets.insert(x.inode, Socket::Tcp(x));
+            }
+        }
+
+        let udp = UdpNetEntries::from_file(path("udp"), si).map_or(Vec::new(), |x| x.0);
+        let udp6 = UdpNetEntries::from_file(path("udp6"), si).map_or(Vec::new(), |x| x.0);
+        for x in udp.into_iter().chain(udp6) {
+            if x.inode != 0 {
+                sockets.insert(x.inode, Socket::Udp(x));
+            }
+        }
+
+        let unix = UnixNetEntries::from_file(path("unix")).map_or(Vec::new(), |x| x.0);
+        for x in unix {
+            if x.inode != 0 {
+                sockets.insert(x.inode, Socket::Unix(x));
+            }
+        }
+
+        let unix_listening = std::fs::File::open(path("unix"))
+            .map(|x| unix_listening(std::io::BufReader::new(x)))
+            .unwrap_or_default();
+
+        Ok(SocketTable {
+            sockets,
+            unix_listening,
+        })
+    }
+
+    pub fn get(&self, inode: u64) -> Option<&Socket> {
+        self.sockets.get(&inode)
+    }
+
+    /// Whether the Unix domain socket is listening for connections
+    pub fn is_unix_listening(&self, inode: u64) -> bool {
+        self.unix_listening.contains(&inode)
+    }
+
+    /// Copy the sockets of `inodes` from `other` to record only the sockets used by processes
+    pub fn extend_from(&mut self, other: &SocketTable, inodes: &[u64]) {
+        for inode in inodes {
+            if let Some(x) = other.get(*inode) {
+                self.sockets.insert(*inode, x.clone());
+            }
+            if other.is_unix_listening(*inode) {
+                self.unix_listening.insert(*inode);
+            }
+        }
+    }
+}
+
+thread_local! {
+    static SHARED_INDEX: RefCell<Weak<RefCell<SocketIndex>>> = const { RefCell::new(Weak::new()) };
+}
+
+/// Socket tables of each network namespace.
+/// The tables are read once per namespace, and the tables of procs are used if the namespace is unknown.
+/// A namespace whose tables can't be read has no table instead of the tables of procs.
+pub struct SocketIndex {
+    procfs: Option<PathBuf>,
+    default: SocketTable,
+    netns: HashMap<u64, Option<SocketTable>>,
+}
+
+impl SocketIndex {
+    pub fn new(procfs: &Option<PathBuf>) -> Self {
+        SocketIndex {
+            procfs: procfs.clone(),
+            default: SocketTable::read(procfs, "net").unwrap_or_default(),
+            netns: HashMap::new(),
+        }
+    }
+
+    /// Socket index shared between columns while they are alive.
+    /// A new index is built at the next collection because the columns are rebuilt.
+    pub fn shared(procfs: &Option<PathBuf>) -> Rc<RefCell<SocketIndex>> {
+        SHARED_INDEX.with(|x| {
+            if let Some(index) = x.borrow().upgrade()
+                && index.borrow().procfs == *procfs
+            {
+                return index;
+            }
+            let index = Rc::new(RefCell::new(SocketIndex::new(procfs)));
+            *x.borrow_mut() = Rc::downgrade(&index);
+            index
+        })
+    }
+
+    /// Socket table in the network namespace of the process.
+    /// The recorded table is used for recorded processes.
+    pub fn table<'a>(&'a mut self, proc: &'a ProcessInfo) -> Option<&'a SocketTable> {
+        if proc.curr_proc.is_record() {
+            return proc.curr_proc.socket_table();
+        }
+        let Some(netns) = crate::util::ns_inode(proc.pid, &self.procfs, "net") else {
+            return Some(&self.default);
+        };
+
+        // The failure is kept too, so that the tables are not read again for each process in the namespace
+        self.netns
+            .entry(netns)
+            .or_insert_with(|| SocketTable::read(&self.procfs, &format!("{}/net", proc.pid)).ok())
+            .as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write;
+
+    fn gen_tcp(n: u64) -> Vec<TcpNetEntry> {
+        let mut text = String::from(
+            "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n",
+        );
+        for i in 0..n {
+            let _ = writeln!(
+                text,
+                "{:4}: 0100007F:{:04X} 0100007F:{:04X} 01 00000000:00000000 00:00000000 00000000  1000        0 {} 1 0000000000000000 20 4 30 10 -1",
+                i,
+                i % 0x10000,
+                (i + 1) % 0x10000,
+                i + 1
+            );
+        }
+        TcpNetEntries::from_read(text.as_bytes(), &crate::util::procfs_system_info(&None))
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn test_bound_addr() {
+        let x = BoundAddr {
+            tcp: true,
+            addr: "0.0.0.0:8080".parse().unwrap(),
+        };
+        assert_eq!(x.to_string(), "tcp4 0.0.0.0:8080");
+        assert!(x.matches("public"));
+        assert!(x.matches("8080"));
+        assert!(x.matches("0.0.0.0:8080"));
+        assert!(!x.matches("80"));
+
+        let x = BoundAddr {
+            tcp: false,
+            addr: "[::1]:9090".parse().unwrap(),
+        };
+        assert_eq!(x.to_string(), "udp6 [::1]:9090");
+        assert!(!x.matches("public"));
+        assert!(x.matches("udp6 [::1]:9090"));
+    }
+
+    #[test]
+    fn test_table() {
+        let entries = gen_tcp(10);
+        let table = SocketTable {
+            sockets: entries
+                .into_iter()
+                .map(|x| (x.inode, Socket::Tcp(x)))
+                .collect(),
+            unix_listening: HashSet::new(),
+        };
+        assert!(matches!(table.get(3), Some(Socket::Tcp(x)) if x.local_address.port() == 2));
+        assert!(table.get(100).is_none());
+    }
+
+    #[test]
+    fn test_unix_listening() {
+        let text = "\
+Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 101 /run/listen.sock
+0000000000000000: 00000002 00000000 00010000 0005 01 102 @/seqpacket
+0000000000000000: 00000002 00000000 00000000 0002 01 103 /run/dgram.sock
+0000000000000000: 00000002 00000000 00000000 0001 01 104 /run/bound.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 105 /run/listen.sock
+0000000000000000: 00000002 00000000 00000000 0001 01 106
+";
+        let listening = unix_listening(text.as_bytes());
+        let mut listening: Vec<_> = listening.into_iter().collect();
+        listening.sort_unstable();
+        // Datagram, bound but unconnected, and connected sockets are not listening
+        assert_eq!(listening, vec![101, 102]);
+    }
+
+    #[test]
+    fn test_index_unreadable_netns() {
+        // procfs where the namespace of PID 1 is known but its tables can't be read
+        let dir = std::env::temp_dir().join(format!("procs_socket_netns_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("1").join("ns")).unwrap();
+        let _ =
+            std::os::unix::fs::symlink("net:[4026531840]", dir.join("1").join("ns").join("net"));
+        let mut index = SocketIndex::new(&Some(dir.clone()));
+
+        // The sockets are unknown instead of being looked up in the tables of procs
+        assert!(index.table(&ProcessInfo::for_test(1)).is_none());
+        assert!(index.netns[&4026531840].is_none());
+        assert!(index.table(&ProcessInfo::for_test(1)).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_index_matches_linear_scan() {
+        let entries = gen_tcp(1000);
+        let mut index = SocketIndex {
+            procfs: None,
+            default: SocketTable {
+                sockets: gen_tcp(1000)
+                    .into_iter()
+                    .map(|x| (x.inode, Socket::Tcp(x)))
+                    .collect(),
+                unix_listening: HashSet::new(),
+            },
+            netns: HashMap::new(),
+        };
+
+        // PID 0 has no network namespace, so the default table is used
+        let proc = ProcessInfo::for_test(0);
+        let table = index.table(&proc).unwrap();
+
+        for inode in 0..1100 {
+            let linear = entries.iter().find(|x| x.inode == inode);
+            let indexed = match table.get(inode) {
+                Some(Socket::Tcp(x)) => Some(x),
+                _ => None,
+            };
+            assert_eq!(linear.is_some(), indexed.is_some());
+            if let (Some(x), Some(y)) = (linear, indexed) {
+                assert_eq!(x.local_address, y.local_address);
+                assert_eq!(x.remote_address, y.remote_address);
+                assert_eq!(x.inode, y.inode);
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::columns::ConfigColumnKind;
+use crate::config::{ColumnInfo, ConfigColumnAlign};
+use crate::filter::find_filter_kind;
+use crate::util::adjust;
+use anyhow::{Error, bail};
+
+#[derive(Debug)]
+struct Placeholder {
+    kind: ConfigColumnKind,
+    raw: bool,
+    align: ConfigColumnAlign,
+    width: Option<usize>,
+}
+
+#[derive(Debug)]
+enum Part {
+    Text(String),
+    Column(Placeholder),
+}
+
+/// Output template like `{pid}\t{user:<10}\t{cpu!raw:>6}`
+///
+/// - `{kind}`: formatted content of the column
+/// - `{kind!raw}`: raw value of the column
+/// - `{kind:<10}`, `{kind:>10}`, `{kind:^10}`: width and alignment
+#[derive(Debug)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut spec = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(x) => spec.push(x),
+                            None => bail!("unclosed placeholder in template: {{{}", spec),
+                        }
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Column(parse_placeholder(&spec)?));
+                }
+                '}' => bail!("unmatched '}}' in template"),
+                '\\' => match chars.next() {
+                    Some('t') => text.push('\t'),
+                    Some('n') => text.push('\n'),
+                    Some(x) => text.push(x),
+                    None => text.push('\\'),
+                },
+                x => text.push(x),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Ok(Template { parts })
+    }
+
+    pub fn kinds(&self, kinds: &mut Vec<ConfigColumnKind>) {
+        for part in &self.parts {
+            if let Part::Column(x) = part
+                && !kinds.contains(&x.kind)
+            {
+                kinds.push(x.kind.clone());
+            }
+        }
+    }
+
+    pub fn render(&self, pid: i32, columns: &[ColumnInfo]) -> String {
+        let mut ret = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(x) => ret.push_str(x),
+                Part::Column(x) => {
+                    let column = columns.iter().find(|c| c.kind == x.kind);
+                    let content = column.and_then(|c| {
+                        if x.raw {
+                            c.column.raw_value(pid).map(|x| x.to_string())
+                        } else {
+                            c.column.display_raw(pid)
+                        }
+                    });
+                    let content = content.unwrap_or_default();
+                    if let Some(width) = x.width {
+                        ret.push_str(&adjust(&content, width, &x.align));
+                    } else {
+                        ret.push_str(&content);
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+fn parse_placeholder(spec: &str) -> Result<Placeholder, Error> {
+    let (name, format) = match spec.split_once(':') {
+        Some((name, format)) => (name, Some(format)),
+        None => (spec, None),
+    };
+    let (name, raw) = match name.split_once('!') {
+        Some((name, "raw")) => (name, true),
+        Some((_, x)) => bail!("unknown modifier in template: !{}", x),
+        None => (name, false),
+    };
+
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("empty placeholder in template");
+    }
+    let Some(kind) = find_filter_kind(name) else {
+        bail!("kind \"{}\" in template is not found", name);
+    };
+
+    let (align, width) = if let Some(format) = format {
+        let (align, width) = match format.chars().next() {
+            Some('<') => (ConfigColumnAlign::Left, &format[1..]),
+            Some('>') => (ConfigColumnAlign::Right, &format[1..]),
+            Some('^') => (ConfigColumnAlign::Center, &format[1..]),
+            _ => (ConfigColumnAlign::Left, format),
+        };
+        let Ok(width) = width.parse::<usize>() else {
+            bail!("invalid width in template: {}", format);
+        };
+        (align, Some(width))
+    } else {
+        (ConfigColumnAlign::Left, None)
+    };
+
+    Ok(Placeholder {
+        kind,
+        raw,
+        align,
+        width,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let template = Template::parse("{pid}\\t{user:<10}|{cpu!raw:>6}{{}}").unwrap();
+        assert_eq!(template.parts.len(), 6);
+        assert!(matches!(&template.parts[1], Part::Text(x) if x == "\t"));
+        assert!(matches!(
+            &template.parts[4],
+            Part::Column(Placeholder {
+                kind: ConfigColumnKind::UsageCpu,
+                raw: true,
+                align: ConfigColumnAlign::Right,
+                width: Some(6),
+            })
+        ));
+        assert!(matches!(&template.parts[5], Part::Text(x) if x == "{}"));
+
+        assert!(Template::parse("{pid").is_err());
+        assert!(Template::parse("pid}").is_err());
+        assert!(Template::parse("{}").is_err());
+        assert!(Template::parse("{pid!fmt}").is_err());
+        assert!(Template::parse("{pid:x}").is_err());
+        assert!(Template::parse("{xxxxxxxx}").is_err());
+    }
+}
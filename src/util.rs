@@ -170,6 +170,19 @@ pub fn adjust(x: &str, len: usize, align: &ConfigColumnAlign) -> String {
     }
 }
 
+/// Convert port list like `[80, 443]` to JSON array
+pub fn port_list_to_json(content: Option<&String>) -> serde_json::Value {
+    match content {
+        Some(x) if !x.is_empty() => x
+            .trim_matches(['[', ']'])
+            .split(',')
+            .filter_map(|x| x.trim().parse::<u16>().ok())
+            .map(serde_json::Value::from)
+            .collect(),
+        _ => serde_json::Value::Null,
+    }
+}
+
 pub fn parse_time(x: u64) -> String {
     let rest = x;
 
@@ -236,6 +249,14 @@ pub fn ansi_trim_end(s: &str) -> String {
 }
 
 pub fn find_column_kind(pat: &str) -> Option<ConfigColumnKind> {
+    let ret = search_column_kind(pat);
+    if ret.is_none() {
+        eprintln!("Can't find column kind: {pat}");
+    }
+    ret
+}
+
+fn search_column_kind(pat: &str) -> Option<ConfigColumnKind> {
     // strict search at first
     for (k, (v, _)) in KIND_LIST.iter() {
         if v.to_lowercase().eq(&pat.to_lowercase()) {
@@ -248,10 +269,54 @@ pub fn find_column_kind(pat: &str) -> Option<ConfigColumnKind> {
             return Some(k.clone());
         }
     }
-    eprintln!("Can't find column kind: {pat}");
     None
 }
 
+// Column kind of a qualified keyword.
+// Unlike `search_column_kind`, only the exact name or a prefix of one kind is accepted,
+// so that an ordinary keyword containing `:` is not qualified by an unrelated column.
+fn qualified_column_kind(field: &str) -> Option<ConfigColumnKind> {
+    let field = field.to_lowercase();
+    for (k, (v, _)) in KIND_LIST.iter() {
+        if v.to_lowercase() == field {
+            return Some(k.clone());
+        }
+    }
+
+    let mut prefixed = KIND_LIST
+        .iter()
+        .filter(|(_, (v, _))| v.to_lowercase().starts_with(&field));
+    match (prefixed.next(), prefixed.next()) {
+        (Some((k, _)), None) => Some(k.clone()),
+        _ => None,
+    }
+}
+
+pub struct QualifiedKeyword {
+    pub kind: ConfigColumnKind,
+    pub keyword: String,
+    pub negative: bool,
+}
+
+/// Parse field-qualified keyword like `user:root`, or `-user:root` / `!user:root` for negation.
+pub fn parse_qualified_keyword(keyword: &str) -> Option<QualifiedKeyword> {
+    let (negative, keyword) = if let Some(x) = keyword.strip_prefix(['-', '!']) {
+        (true, x)
+    } else {
+        (false, keyword)
+    };
+    let (field, value) = keyword.split_once(':')?;
+    if field.is_empty() || value.is_empty() || !field.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let kind = qualified_column_kind(field)?;
+    Some(QualifiedKeyword {
+        kind,
+        keyword: value.to_string(),
+        negative,
+    })
+}
+
 #[cfg(target_os = "macos")]
 pub fn change_endian(val: u32) -> u32 {
     let mut ret = 0;
@@ -383,3 +448,244 @@ pub fn process_new(
         procfs::process::Process::new(pid)
     }
 }
+
+/// Path of a system-wide file like `net/tcp` in procfs
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn procfs_file(procfs: &Option<std::path::PathBuf>, name: &str) -> std::path::PathBuf {
+    if let Some(x) = procfs {
+        x.join(name)
+    } else {
+        std::path::Path::new("/proc").join(name)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn procfs_boot_time(
+    procfs: &Option<std::path::PathBuf>,
+) -> procfs::ProcResult<chrono::DateTime<chrono::Local>> {
+    use chrono::TimeZone;
+    use procfs::FromReadSI;
+
+    let stat =
+        procfs::KernelStats::from_file(procfs_file(procfs, "stat"), procfs::current_system_info())?;
+    chrono::Local
+        .timestamp_opt(stat.btime as i64, 0)
+        .single()
+        .ok_or_else(|| procfs::ProcError::Other(String::from("invalid boot time")))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+type SystemInfoCache = Option<(Option<std::path::PathBuf>, procfs::ExplicitSystemInfo)>;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+thread_local! {
+    static SYSTEM_INFO: std::cell::RefCell<SystemInfoCache> = const { std::cell::RefCell::new(None) };
+}
+
+/// System information like clock ticks of the system which procfs belongs to.
+/// Clock ticks and page size are read from the auxiliary vector of a process in procfs.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn procfs_system_info(procfs: &Option<std::path::PathBuf>) -> procfs::ExplicitSystemInfo {
+    if let Some((path, info)) = SYSTEM_INFO.with_borrow(|x| x.clone())
+        && path == *procfs
+    {
+        return info;
+    }
+
+    // Keys of the auxiliary vector in <elf.h>
+    const AT_PAGESZ: u64 = 6;
+    const AT_CLKTCK: u64 = 17;
+
+    let local = procfs::current_system_info();
+    let info = if let Some(root) = procfs {
+        // auxv of other users' processes is not readable, so `self` and PID 1 are tried first
+        let auxv = ["self", "1"]
+            .iter()
+            .find_map(|x| {
+                procfs::process::Process::new_with_root(root.join(x))
+                    .ok()?
+                    .auxv()
+                    .ok()
+            })
+            .or_else(|| {
+                procfs::process::all_processes_with_root(root)
+                    .ok()?
+                    .flatten()
+                    .find_map(|x| x.auxv().ok())
+            });
+        if auxv.is_none() {
+            eprintln!(
+                "Warning: clock ticks and page size of the current system are used because no auxv in {root:?} is readable"
+            );
+        }
+        let auxv = |key: u64| {
+            auxv.as_ref()
+                .and_then(|x| x.get(&key).copied())
+                .filter(|x| *x != 0)
+        };
+        procfs::ExplicitSystemInfo {
+            boot_time_secs: procfs_boot_time(procfs).map_or(0, |x| x.timestamp() as u64),
+            ticks_per_second: auxv(AT_CLKTCK).unwrap_or_else(|| local.ticks_per_second()),
+            page_size: auxv(AT_PAGESZ).unwrap_or_else(|| local.page_size()),
+            is_little_endian: local.is_little_endian(),
+        }
+    } else {
+        procfs::ExplicitSystemInfo {
+            boot_time_secs: local.boot_time_secs().unwrap_or(0),
+            ticks_per_second: local.ticks_per_second(),
+            page_size: local.page_size(),
+            is_little_endian: local.is_little_endian(),
+        }
+    };
+
+    SYSTEM_INFO.set(Some((procfs.clone(), info)));
+    info
+}
+
+/// State of the system which is shown.
+/// During replay, it is the state recorded with the snapshot instead of the current host.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, serde_derive::Serialize, serde_derive::Deserialize,
+)]
+pub struct SystemState {
+    /// Unix time in milliseconds when processes are collected
+    pub time: i64,
+    /// Unix time in seconds
+    pub boot_time: i64,
+    pub ticks_per_second: u64,
+    pub page_size: u64,
+    /// Total memory in bytes
+    pub mem_total: u64,
+}
+
+/// State of the current system which procfs belongs to
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn system_state(procfs: &Option<std::path::PathBuf>) -> SystemState {
+    use procfs::FromRead;
+
+    let info = procfs_system_info(procfs);
+    let meminfo = procfs::Meminfo::from_file(procfs_file(procfs, "meminfo"));
+    SystemState {
+        time: chrono::Local::now().timestamp_millis(),
+        boot_time: procfs_boot_time(procfs).map_or(0, |x| x.timestamp()),
+        ticks_per_second: info.ticks_per_second,
+        page_size: info.page_size,
+        mem_total: meminfo.map_or(0, |x| x.mem_total),
+    }
+}
+
+/// State of the current system.
+/// Only the time is filled because columns read the system by themselves on other than Linux.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn system_state(_procfs: &Option<std::path::PathBuf>) -> SystemState {
+    SystemState {
+        time: chrono::Local::now().timestamp_millis(),
+        ..Default::default()
+    }
+}
+
+/// Inode number of the namespace like `net` which the process belongs to
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn ns_inode(pid: i32, procfs: &Option<std::path::PathBuf>, ns: &str) -> Option<u64> {
+    let link = std::fs::read_link(procfs_file(procfs, &format!("{pid}/ns/{ns}"))).ok()?;
+    let link = link.to_str()?;
+    link.strip_prefix(ns)?
+        .strip_prefix(":[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_parse_qualified_keyword() {
+        use super::*;
+
+        let x = parse_qualified_keyword("user:root").unwrap();
+        assert!(x.kind == ConfigColumnKind::User && x.keyword == "root" && !x.negative);
+        let x = parse_qualified_keyword("!Command:nginx").unwrap();
+        assert!(x.kind == ConfigColumnKind::Command && x.keyword == "nginx" && x.negative);
+        // Unique prefix of a kind
+        let x = parse_qualified_keyword("-comm:nginx").unwrap();
+        assert!(x.kind == ConfigColumnKind::Command && x.negative);
+
+        // Substring of a kind like `VmRss`, ambiguous prefix and unknown word are ordinary keywords
+        assert!(parse_qualified_keyword("rss:foo").is_none());
+        assert!(parse_qualified_keyword("vm:foo").is_none());
+        assert!(parse_qualified_keyword("foo:bar").is_none());
+        assert!(parse_qualified_keyword("user:").is_none());
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_procfs_boot_time() {
+        let dir = std::env::temp_dir().join(format!("procs_procfs_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("stat"),
+            "cpu  1 2 3 4 5 6 7 0 0 0\ncpu0 1 2 3 4 5 6 7 0 0 0\nintr 0\nctxt 0\nbtime 1000000000\nprocesses 1\nprocs_running 1\nprocs_blocked 0\n",
+        )
+        .unwrap();
+
+        let procfs = Some(dir.clone());
+        assert_eq!(
+            super::procfs_file(&procfs, "net/tcp"),
+            dir.join("net").join("tcp")
+        );
+        let boot_time = super::procfs_boot_time(&procfs).unwrap();
+        assert_eq!(boot_time.timestamp(), 1000000000);
+
+        // Auxiliary vector with AT_PAGESZ and AT_CLKTCK
+        std::fs::create_dir_all(dir.join("1")).unwrap();
+        let auxv: Vec<u8> = [6u64, 65536, 17, 1000, 0, 0]
+            .iter()
+            .flat_map(|x| x.to_ne_bytes())
+            .collect();
+        std::fs::write(dir.join("1").join("auxv"), auxv).unwrap();
+        let info = super::procfs_system_info(&procfs);
+        assert_eq!(info.boot_time_secs, 1000000000);
+        assert_eq!(info.ticks_per_second, 1000);
+        assert_eq!(info.page_size, 65536);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_procfs_system_info_auxv() {
+        let dir = std::env::temp_dir().join(format!("procs_auxv_{}", std::process::id()));
+        let write_auxv = |root: &str, pid: &str, page_size: u64| {
+            let dir = dir.join(root).join(pid);
+            std::fs::create_dir_all(&dir).unwrap();
+            let auxv: Vec<u8> = [6u64, page_size, 17, 100, 0, 0]
+                .iter()
+                .flat_map(|x| x.to_ne_bytes())
+                .collect();
+            std::fs::write(dir.join("auxv"), auxv).unwrap();
+        };
+
+        // Other processes are scanned if neither `self` nor PID 1 is readable
+        write_auxv("a", "42", 8192);
+        let procfs = Some(dir.join("a"));
+        assert_eq!(super::procfs_system_info(&procfs).page_size, 8192);
+
+        // `self` is preferred
+        write_auxv("b", "42", 16384);
+        write_auxv("b", "1", 65536);
+        std::os::unix::fs::symlink("42", dir.join("b").join("self")).unwrap();
+        let procfs = Some(dir.join("b"));
+        assert_eq!(super::procfs_system_info(&procfs).page_size, 16384);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_ns_inode() {
+        let pid = std::process::id() as i32;
+        assert!(super::ns_inode(pid, &None, "net").is_some());
+        assert!(super::ns_inode(pid, &None, "xxx").is_none());
+    }
+}
@@ -2,20 +2,30 @@ use crate::Opt;
 use crate::column::Column;
 use crate::columns::*;
 use crate::config::*;
-use crate::opt::{ArgColorMode, ArgPagerMode};
-use crate::process::collect_proc;
+use crate::diff::{Baseline, DiffKind, compare};
+use crate::filter::Expr;
+use crate::opt::{ArgColorMode, ArgFormat, ArgPagerMode};
+use crate::process::{DataSource, ProcessInfo, collect_proc};
 use crate::search_regex::SearchRegex;
 use crate::style::{apply_color, apply_style, color_to_column_style};
+use crate::template::Template;
 use crate::term_info::TermInfo;
 use crate::util::{
-    KeywordClass, ansi_trim_end, classify, find_column_kind, find_exact, find_partial,
-    has_regex_syntax, truncate,
+    KeywordClass, QualifiedKeyword, SystemState, ansi_trim_end, classify, find_column_kind,
+    find_exact, find_partial, has_regex_syntax, lap, parse_qualified_keyword, truncate,
 };
-use anyhow::{Error, bail};
+use anyhow::{Context, Error, bail};
+use chrono::Local;
 #[cfg(not(target_os = "windows"))]
 use pager::Pager;
-use std::collections::HashMap;
-use std::time::Duration;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+thread_local! {
+    // Unavailable columns of qualified keywords which have been warned
+    static WARNED_KINDS: RefCell<HashSet<ConfigColumnKind>> = RefCell::new(HashSet::new());
+}
 
 pub struct SortInfo {
     pub idx: usize,
@@ -30,6 +40,11 @@ pub struct View {
     pub auxiliary_pids: Vec<i32>,
     pub parent_pids: HashMap<i32, i32>,
     pub child_pids: HashMap<i32, Vec<i32>>,
+    pub filter_expr: Option<Expr>,
+    pub template: Option<Template>,
+    pub diff: Option<HashMap<i32, DiffKind>>,
+    proc: Vec<ProcessInfo>,
+    system_state: SystemState,
 }
 
 impl View {
@@ -38,6 +53,35 @@ impl View {
         let mut columns = Vec::new();
         let mut only_kind_found = false;
 
+        let show_thread = if opt.thread {
+            true
+        } else if opt.tree {
+            config.display.show_thread_in_tree
+        } else {
+            config.display.show_thread
+        };
+
+        // Columns show the system state like boot time in the snapshot during replay,
+        // so the snapshot is loaded before columns are created.
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let replay = if let Some(path) = &opt.replay {
+            Some(crate::record::load(
+                path,
+                opt.replay_index,
+                show_thread,
+                config.display.show_kthreads,
+            )?)
+        } else {
+            None
+        };
+        // The state is shared by all columns instead of being read by each column
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let system_state = replay
+            .as_ref()
+            .map_or_else(|| crate::util::system_state(&opt.procfs), |(_, x)| *x);
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        let system_state = crate::util::system_state(&opt.procfs);
+
         // Override style of TreeSlot
         let tree_slot = ConfigColumn {
             kind: ConfigColumnKind::TreeSlot,
@@ -48,6 +92,7 @@ impl View {
             max_width: None,
             min_width: None,
             header: None,
+            mode: None,
         };
 
         // Adding the sort column to inserts if not already present
@@ -130,7 +175,7 @@ impl View {
                     true
                 };
 
-                let column = gen_column(
+                let mut column = gen_column(
                     &kind,
                     c.header.clone(),
                     &config.docker.path,
@@ -138,7 +183,13 @@ impl View {
                     config.display.abbr_sid,
                     &config.display.tree_symbols,
                     opt.procfs.clone(),
+                    system_state,
                 );
+                if let Some(mode) = &c.mode {
+                    column
+                        .set_mode(mode)
+                        .context(format!("failed to set mode of \"{}\"", KIND_LIST[&kind].0))?;
+                }
                 if column.available() {
                     columns.push(ColumnInfo {
                         column,
@@ -168,20 +219,108 @@ impl View {
             bail!("kind \"{}\" is not found in columns", only_kind);
         }
 
-        let show_thread = if opt.thread {
-            true
-        } else if opt.tree {
-            config.display.show_thread_in_tree
+        let filter_expr = if let Some(filter) = &opt.filter {
+            Some(Expr::parse(filter, &config.search.case)?)
         } else {
-            config.display.show_thread
+            None
+        };
+
+        let template = if let Some(template) = &opt.format_template {
+            let template = config.templates.get(template).unwrap_or(template);
+            Some(Template::parse(template)?)
+        } else {
+            None
+        };
+
+        // Add hidden columns which are referred by filter expression, qualified keywords or template only
+        let mut hidden_kinds = Vec::new();
+        if let Some(expr) = &filter_expr {
+            expr.kinds(&mut hidden_kinds);
+        }
+        if let Some(template) = &template {
+            template.kinds(&mut hidden_kinds);
+        }
+        for k in &opt.keyword {
+            if let Some(x) = parse_qualified_keyword(k)
+                && !hidden_kinds.contains(&x.kind)
+            {
+                hidden_kinds.push(x.kind);
+            }
+        }
+        for kind in hidden_kinds {
+            if columns.iter().any(|x| x.kind == kind) {
+                continue;
+            }
+            let column = gen_column(
+                &kind,
+                None,
+                &config.docker.path,
+                &config.display.separator,
+                config.display.abbr_sid,
+                &config.display.tree_symbols,
+                opt.procfs.clone(),
+                system_state,
+            );
+            if column.available() {
+                columns.push(ColumnInfo {
+                    column,
+                    kind,
+                    style: ConfigColumnStyle::ByUnit,
+                    nonnumeric_search: false,
+                    numeric_search: false,
+                    align: ConfigColumnAlign::Left,
+                    max_width: None,
+                    min_width: None,
+                    visible: false,
+                });
+            }
+        }
+
+        // Read only the data required by columns, and the data kept by recording
+        let data_source = columns.iter().fold(DataSource::default(), |x, c| {
+            x.merge(c.column.data_source())
+        });
+        let data_source = if opt.record.is_some() {
+            data_source.merge(DataSource::RECORD)
+        } else {
+            data_source
         };
 
+        let mut time = Instant::now();
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let proc = if let Some((proc, _)) = replay {
+            proc
+        } else {
+            collect_proc(
+                Duration::from_millis(opt.interval),
+                show_thread,
+                config.display.show_kthreads,
+                &opt.procfs,
+                config.collect.workers(),
+                data_source,
+                opt.watch_mode,
+            )
+        };
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
         let proc = collect_proc(
             Duration::from_millis(opt.interval),
             show_thread,
             config.display.show_kthreads,
             &opt.procfs,
+            config.collect.workers(),
+            data_source,
+            opt.watch_mode,
         );
+
+        if opt.debug {
+            lap(&mut time, "Info: collect_proc");
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if let Some(path) = &opt.record {
+            crate::record::save(path, &proc, system_state, &opt.procfs)?;
+        }
         for c in columns.iter_mut() {
             for p in &proc {
                 c.column.add(p);
@@ -208,7 +347,7 @@ impl View {
             sort_info.idx = 0;
         }
 
-        Ok(View {
+        let mut view = View {
             columns,
             term_info,
             sort_info,
@@ -216,7 +355,65 @@ impl View {
             auxiliary_pids: vec![],
             parent_pids,
             child_pids,
-        })
+            filter_expr,
+            template,
+            diff: None,
+            proc,
+            system_state,
+        };
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if let Some(path) = &opt.diff_from {
+            let (baseline, state) =
+                crate::record::load(path, 0, show_thread, config.display.show_kthreads)?;
+            view.apply_diff(Some((baseline, state)), opt, config);
+        }
+
+        Ok(view)
+    }
+
+    /// Compare with the baseline processes, and show the differences only.
+    /// All processes are regarded as unchanged if there is no baseline.
+    /// Without baseline like the first update of watch mode, all processes are shown without marker
+    pub fn apply_diff(&mut self, baseline: Option<Baseline>, opt: &Opt, config: &Config) {
+        let Some((baseline, baseline_state)) = baseline else {
+            self.diff = None;
+            return;
+        };
+
+        // A disappeared process whose PID is reused by an appeared process is not shown
+        // because processes are distinguished by PID in the view.
+        let procs: HashSet<i32> = self.proc.iter().map(|p| p.pid).collect();
+        let diff: HashMap<i32, DiffKind> = compare(
+            (&baseline, baseline_state),
+            (&self.proc, self.system_state),
+            opt,
+            config,
+        )
+        .into_iter()
+        .filter(|(pid, kind)| !(*kind == DiffKind::Disappeared && procs.contains(pid)))
+        .collect();
+
+        // Disappeared processes are shown with the data of the baseline
+        for p in &baseline {
+            if diff.get(&p.pid) != Some(&DiffKind::Disappeared) {
+                continue;
+            }
+            for c in self.columns.iter_mut() {
+                c.column.add(p);
+            }
+            if opt.tree || !config.display.show_self_parents {
+                self.parent_pids.insert(p.pid, p.ppid);
+                self.child_pids.entry(p.ppid).or_default().push(p.pid);
+            }
+        }
+
+        self.diff = Some(diff);
+    }
+
+    /// Take the collected processes to use them as the baseline of the next update
+    pub fn take_proc(&mut self) -> Baseline {
+        (std::mem::take(&mut self.proc), self.system_state)
     }
 
     pub fn filter(&mut self, opt: &Opt, config: &Config, header_lines: usize) -> Result<(), Error> {
@@ -238,24 +435,45 @@ impl View {
 
         let mut keyword_nonnumeric = Vec::new();
         let mut keyword_numeric = Vec::new();
+        let mut keyword_qualified = Vec::new();
 
         for k in &opt.keyword {
+            if let Some(x) = parse_qualified_keyword(k) {
+                let column = self.qualified_column(&x);
+                keyword_qualified.push((column, x));
+                continue;
+            }
             match classify(k) {
                 KeywordClass::Numeric => keyword_numeric.push(k),
                 KeywordClass::NonNumeric => keyword_nonnumeric.push(k),
             }
         }
 
+        // Regex is applied to the qualified column only if the keyword is qualified
+        let regex_target = opt
+            .keyword
+            .first()
+            .map(|k| match parse_qualified_keyword(k) {
+                Some(x) => {
+                    let column = self.qualified_column(&x);
+                    (x.keyword.clone(), Some((column, x.negative)))
+                }
+                None => (k.clone(), None),
+            });
+
         let regex_mode = if opt.regex {
             true
         } else if opt.smart {
-            opt.keyword.len() == 1 && has_regex_syntax(&opt.keyword[0])
+            opt.keyword.len() == 1
+                && regex_target
+                    .as_ref()
+                    .map(|(x, _)| has_regex_syntax(x))
+                    .unwrap_or(false)
         } else {
             false
         };
 
-        let regex = if regex_mode && !opt.keyword.is_empty() {
-            let pattern = &opt.keyword[0];
+        let regex = if regex_mode && let Some((pattern, _)) = &regex_target {
             let ignore_case = match config.search.case {
                 ConfigSearchCase::Smart => pattern == &pattern.to_ascii_lowercase(),
                 ConfigSearchCase::Insensitive => true,
@@ -309,15 +527,31 @@ impl View {
 
             let candidate = if hidden_process {
                 false
+            } else if let Some(diff) = &self.diff
+                && !diff.contains_key(pid)
+            {
+                false
+            } else if let Some(expr) = &self.filter_expr
+                && !expr.eval(*pid, &self.columns)?
+            {
+                false
             } else if opt.keyword.is_empty() {
                 true
             } else if let Some(regex) = &regex {
-                View::search_regex(*pid, cols_searchable.as_slice(), regex)?
+                match regex_target.as_ref().and_then(|(_, x)| x.as_ref()) {
+                    Some((Some(column), negative)) => {
+                        View::search_regex(*pid, &[*column], regex)? != *negative
+                    }
+                    // Unavailable column matches nothing
+                    Some((None, negative)) => *negative,
+                    None => View::search_regex(*pid, cols_searchable.as_slice(), regex)?,
+                }
             } else {
                 View::search(
                     *pid,
                     &keyword_numeric,
                     &keyword_nonnumeric,
+                    &keyword_qualified,
                     cols_numeric.as_slice(),
                     cols_nonnumeric.as_slice(),
                     config,
@@ -359,7 +593,10 @@ impl View {
             }
 
             let reserved_rows = 4 + header_lines;
-            if opt.watch_mode && visible_pids.len() >= self.term_info.height - reserved_rows {
+            if opt.watch_mode
+                && !opt.json_lines
+                && visible_pids.len() >= self.term_info.height - reserved_rows
+            {
                 break;
             }
         }
@@ -413,7 +650,32 @@ impl View {
     ) -> Result<(), Error> {
         if opt.json {
             self.term_info.use_pager = false;
-            self.display_json()?;
+            if opt.tree {
+                self.display_json_tree()?;
+            } else {
+                self.display_json()?;
+            }
+            return Ok(());
+        }
+
+        if opt.json_lines {
+            self.term_info.use_pager = false;
+            self.display_json_lines()?;
+            return Ok(());
+        }
+
+        if let Some(template) = &self.template {
+            self.term_info.use_pager = false;
+            for pid in &self.visible_pids {
+                self.term_info
+                    .write_line(&template.render(*pid, &self.columns))?;
+            }
+            return Ok(());
+        }
+
+        if let Some(format) = &opt.format {
+            self.term_info.use_pager = false;
+            self.display_delimited(format, opt.no_header)?;
             return Ok(());
         }
 
@@ -522,7 +784,7 @@ impl View {
     }
 
     fn display_header(&self, config: &Config, theme: &ConfigTheme) -> Result<(), Error> {
-        let mut row = String::new();
+        let mut row = self.diff_marker(None);
         for (i, c) in self.columns.iter().enumerate() {
             if c.visible {
                 let order = if i == self.sort_info.idx {
@@ -549,7 +811,7 @@ impl View {
     }
 
     fn display_unit(&self, config: &Config, theme: &ConfigTheme) -> Result<(), Error> {
-        let mut row = String::new();
+        let mut row = self.diff_marker(None);
         for c in &self.columns {
             if c.visible {
                 row = format!(
@@ -577,7 +839,7 @@ impl View {
         theme: &ConfigTheme,
         auxiliary: bool,
     ) -> Result<(), Error> {
-        let mut row = String::new();
+        let mut row = self.diff_marker(Some(pid));
         for c in &self.columns {
             if c.visible {
                 row = format!(
@@ -604,19 +866,54 @@ impl View {
 
         let len_pid = self.visible_pids.len();
         for (i, pid) in self.visible_pids.iter().enumerate() {
-            let mut line = "{".to_string();
-            let len_column = self.columns.len();
-            for (j, c) in self.columns.iter().enumerate() {
-                if c.visible && c.kind != ConfigColumnKind::Separator {
-                    let text = c.column.display_json(*pid);
-                    line.push_str(&text);
-                    if j != len_column - 1 {
-                        line.push_str(", ");
-                    }
+            let mut line = serde_json::to_string(&self.json_object(*pid))?;
+            if i != len_pid - 1 {
+                line.push(',');
+            }
+            self.term_info.write_line(&line)?;
+        }
+
+        self.term_info.write_line("]")?;
+        Ok(())
+    }
+
+    fn display_json_lines(&self) -> Result<(), Error> {
+        let timestamp = Local::now().to_rfc3339();
+        for pid in &self.visible_pids {
+            let mut object = serde_json::Map::new();
+            object.insert(
+                "timestamp".to_string(),
+                serde_json::Value::from(timestamp.as_str()),
+            );
+            object.append(&mut self.json_object(*pid));
+            self.term_info
+                .write_line(&serde_json::to_string(&object)?)?;
+        }
+        Ok(())
+    }
+
+    fn display_json_tree(&self) -> Result<(), Error> {
+        let visible: HashSet<i32> = self.visible_pids.iter().copied().collect();
+        let auxiliary: HashSet<i32> = self.auxiliary_pids.iter().copied().collect();
+
+        // Visible children of each process in display order
+        let mut children = HashMap::<i32, Vec<i32>>::new();
+        let mut roots = Vec::new();
+        for pid in &self.visible_pids {
+            match self.parent_pids.get(pid) {
+                Some(ppid) if ppid != pid && visible.contains(ppid) => {
+                    children.entry(*ppid).or_default().push(*pid);
                 }
+                _ => roots.push(*pid),
             }
-            line.push('}');
-            if i != len_pid - 1 {
+        }
+
+        self.term_info.write_line("[")?;
+
+        let len_roots = roots.len();
+        for (i, pid) in roots.iter().enumerate() {
+            let mut line = serde_json::to_string(&self.json_tree(*pid, &children, &auxiliary))?;
+            if i != len_roots - 1 {
                 line.push(',');
             }
             self.term_info.write_line(&line)?;
@@ -626,6 +923,116 @@ impl View {
         Ok(())
     }
 
+    fn json_tree(
+        &self,
+        pid: i32,
+        children: &HashMap<i32, Vec<i32>>,
+        auxiliary: &HashSet<i32>,
+    ) -> serde_json::Value {
+        let mut object = self.json_object(pid);
+        object.insert(
+            "auxiliary".to_string(),
+            serde_json::Value::from(auxiliary.contains(&pid)),
+        );
+        let children: Vec<_> = children
+            .get(&pid)
+            .map(|x| {
+                x.iter()
+                    .map(|x| self.json_tree(*x, children, auxiliary))
+                    .collect()
+            })
+            .unwrap_or_default();
+        object.insert("children".to_string(), serde_json::Value::from(children));
+        serde_json::Value::Object(object)
+    }
+
+    fn json_object(&self, pid: i32) -> serde_json::Map<String, serde_json::Value> {
+        let mut object = serde_json::Map::new();
+        if let Some(diff) = &self.diff {
+            let marker = diff.get(&pid).map(|x| x.marker()).unwrap_or_default();
+            object.insert("diff".to_string(), serde_json::Value::from(marker));
+        }
+        for c in self.data_columns() {
+            let (name, _) = KIND_LIST[&c.kind];
+            object.insert(name.to_string(), c.column.display_json(pid));
+        }
+        object
+    }
+
+    // Marker column of diff mode. Header and unit lines have blank.
+    fn diff_marker(&self, pid: Option<i32>) -> String {
+        let Some(diff) = &self.diff else {
+            return String::new();
+        };
+        match pid.and_then(|x| diff.get(&x)) {
+            Some(x @ DiffKind::Appeared) => console::style(x.marker()).green().to_string(),
+            Some(x @ DiffKind::Disappeared) => console::style(x.marker()).red().to_string(),
+            Some(x @ DiffKind::Changed) => console::style(x.marker()).yellow().to_string(),
+            None => String::from(" "),
+        }
+    }
+
+    // Visible columns except decorations like separators and tree
+    fn data_columns(&self) -> impl Iterator<Item = &ColumnInfo> {
+        self.columns.iter().filter(|c| {
+            c.visible
+                && !matches!(
+                    c.kind,
+                    ConfigColumnKind::Separator
+                        | ConfigColumnKind::Tree
+                        | ConfigColumnKind::TreeSlot
+                )
+        })
+    }
+
+    fn display_delimited(&self, format: &ArgFormat, no_header: bool) -> Result<(), Error> {
+        for line in self.delimited_lines(format, no_header) {
+            self.term_info.write_line(&line)?;
+        }
+        Ok(())
+    }
+
+    fn delimited_lines(&self, format: &ArgFormat, no_header: bool) -> Vec<String> {
+        let delimiter = match format {
+            ArgFormat::Csv => ',',
+            ArgFormat::Tsv => '\t',
+        };
+
+        // RFC 4180 quoting
+        let quote = |x: &str| {
+            if x.contains([delimiter, '"', '\r', '\n']) {
+                format!("\"{}\"", x.replace('"', "\"\""))
+            } else {
+                x.to_string()
+            }
+        };
+
+        let columns: Vec<_> = self.data_columns().collect();
+        let mut lines = Vec::new();
+
+        if !no_header {
+            let headers: Vec<_> = columns.iter().map(|c| quote(c.column.header())).collect();
+            lines.push(headers.join(&delimiter.to_string()));
+        }
+
+        for pid in &self.visible_pids {
+            let mut fields = Vec::new();
+            for c in &columns {
+                let field = if c.column.is_numeric() {
+                    c.column
+                        .raw_value(*pid)
+                        .map(|x| x.to_string())
+                        .unwrap_or_default()
+                } else {
+                    c.column.display_raw(*pid).unwrap_or_default()
+                };
+                fields.push(quote(&field));
+            }
+            lines.push(fields.join(&delimiter.to_string()));
+        }
+        lines
+    }
+
     fn get_sort_info(opt: &Opt, config: &Config, cols: &[ColumnInfo]) -> SortInfo {
         let (mut sort_idx, sort_order) = match (&opt.sorta, &opt.sortd) {
             (Some(sort), _) | (_, Some(sort)) => {
@@ -661,10 +1068,12 @@ impl View {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn search<T: AsRef<str>>(
         pid: i32,
         keyword_numeric: &[T],
         keyword_nonnumeric: &[T],
+        keyword_qualified: &[(Option<&dyn Column>, QualifiedKeyword)],
         cols_numeric: &[&dyn Column],
         cols_nonnumeric: &[&dyn Column],
         config: &Config,
@@ -702,17 +1111,79 @@ impl View {
                 &config.search.case,
             ),
         };
+        let mut ret_qualified = match logic {
+            ConfigSearchLogic::And | ConfigSearchLogic::Nand => true,
+            ConfigSearchLogic::Or | ConfigSearchLogic::Nor => false,
+        };
+        for (column, qualified) in keyword_qualified {
+            let keyword = [&qualified.keyword];
+            let search_kind = match classify(&qualified.keyword) {
+                KeywordClass::Numeric => &config.search.numeric_search,
+                KeywordClass::NonNumeric => &config.search.nonnumeric_search,
+            };
+            // Unavailable column matches nothing
+            let Some(column) = column else {
+                ret_qualified = match logic {
+                    ConfigSearchLogic::And | ConfigSearchLogic::Nand => {
+                        ret_qualified & qualified.negative
+                    }
+                    ConfigSearchLogic::Or | ConfigSearchLogic::Nor => {
+                        ret_qualified | qualified.negative
+                    }
+                };
+                continue;
+            };
+            let hit = match search_kind {
+                ConfigSearchKind::Partial => find_partial(
+                    &[*column],
+                    pid,
+                    &keyword,
+                    &ConfigSearchLogic::And,
+                    &config.search.case,
+                ),
+                ConfigSearchKind::Exact => find_exact(
+                    &[*column],
+                    pid,
+                    &keyword,
+                    &ConfigSearchLogic::And,
+                    &config.search.case,
+                ),
+            };
+            let hit = hit != qualified.negative;
+            ret_qualified = match logic {
+                ConfigSearchLogic::And | ConfigSearchLogic::Nand => ret_qualified & hit,
+                ConfigSearchLogic::Or | ConfigSearchLogic::Nor => ret_qualified | hit,
+            };
+        }
         match logic {
-            ConfigSearchLogic::And => ret_nonnumeric & ret_numeric,
-            ConfigSearchLogic::Or => ret_nonnumeric | ret_numeric,
-            ConfigSearchLogic::Nand => !(ret_nonnumeric & ret_numeric),
-            ConfigSearchLogic::Nor => !(ret_nonnumeric | ret_numeric),
+            ConfigSearchLogic::And => ret_nonnumeric & ret_numeric & ret_qualified,
+            ConfigSearchLogic::Or => ret_nonnumeric | ret_numeric | ret_qualified,
+            ConfigSearchLogic::Nand => !(ret_nonnumeric & ret_numeric & ret_qualified),
+            ConfigSearchLogic::Nor => !(ret_nonnumeric | ret_numeric | ret_qualified),
         }
     }
 
+    // Column of the qualified keyword. A warning is shown once if the column is not available.
+    fn qualified_column(&self, keyword: &QualifiedKeyword) -> Option<&dyn Column> {
+        let column = self.columns.iter().find(|c| c.kind == keyword.kind);
+        if column.is_none() {
+            WARNED_KINDS.with_borrow_mut(|x| {
+                if x.insert(keyword.kind.clone()) {
+                    eprintln!(
+                        "Warning: column \"{}\" is not available, so it matches no process",
+                        KIND_LIST[&keyword.kind].0
+                    );
+                }
+            });
+        }
+        column.map(|c| c.column.as_ref())
+    }
+
     fn search_regex(pid: i32, cols: &[&dyn Column], regex: &SearchRegex) -> Result<bool, Error> {
         for c in cols {
-            if regex.is_match(&c.display_json(pid))? {
+            if let Some(x) = c.display_raw(pid)
+                && regex.is_match(&x)?
+            {
                 return Ok(true);
             }
         }
@@ -755,7 +1226,7 @@ impl View {
 
         for i in 1..max_idx {
             let idx = (current + i) % max_idx;
-            if self.columns[idx].column.sortable() {
+            if self.columns[idx].column.sortable() && self.columns[idx].visible {
                 return idx;
             }
         }
@@ -768,10 +1239,345 @@ impl View {
 
         for i in 1..max_idx {
             let idx = (current + max_idx - i) % max_idx;
-            if self.columns[idx].column.sortable() {
+            if self.columns[idx].column.sortable() && self.columns[idx].visible {
                 return idx;
             }
         }
         current
     }
 }
+
+#[cfg(test)]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use clap::Parser;
+
+    // View which shows only this process
+    fn view_self(args: &[&str], config: &str) -> (View, i32) {
+        let pid = std::process::id() as i32;
+        let pid_str = pid.to_string();
+        let mut config: Config = toml::from_str(config).unwrap();
+        config.pager.mode = ConfigPagerMode::Disable;
+        config.display.show_self = true;
+
+        let args = [&["procs"], args, &[pid_str.as_str()]].concat();
+        let mut opt = Opt::parse_from(args.iter());
+        let mut view = View::new(&mut opt, &config, false).unwrap();
+        view.filter(&opt, &config, 1).unwrap();
+        assert_eq!(view.visible_pids, vec![pid]);
+        (view, pid)
+    }
+
+    // PIDs selected by keywords
+    fn filtered_pids(args: &[&str], config: &str) -> Result<Vec<i32>, Error> {
+        let mut config: Config = toml::from_str(config).unwrap();
+        config.pager.mode = ConfigPagerMode::Disable;
+        config.display.show_self = true;
+        config.display.show_self_parents = true;
+
+        let args = [&["procs"], args].concat();
+        let mut opt = Opt::parse_from(args.iter());
+        let mut view = View::new(&mut opt, &config, false)?;
+        view.filter(&opt, &config, 1)?;
+        Ok(view.visible_pids)
+    }
+
+    #[test]
+    fn test_filter_qualified() {
+        let config = r#"
+[[columns]]
+kind = "Pid"
+[[columns]]
+kind = "Command"
+"#;
+        let pid = std::process::id() as i32;
+        let ppid = std::os::unix::process::parent_id() as i32;
+        let pid_key = format!("pid:{pid}");
+        let ppid_key = format!("pid:{ppid}");
+        let own_ppid_key = format!("ppid:{ppid}");
+        let bad_ppid_key = format!("ppid:{pid}");
+
+        // PPid is not shown, but it can be used as qualifier
+        assert_eq!(filtered_pids(&[&pid_key], config).unwrap(), vec![pid]);
+        assert_eq!(
+            filtered_pids(&["--and", &pid_key, &own_ppid_key], config).unwrap(),
+            vec![pid]
+        );
+        assert!(
+            filtered_pids(&["--and", &pid_key, &bad_ppid_key], config)
+                .unwrap()
+                .is_empty()
+        );
+        let mut pids = filtered_pids(&["--or", &pid_key, &ppid_key], config).unwrap();
+        pids.sort_unstable();
+        let mut expected = vec![pid, ppid];
+        expected.sort_unstable();
+        assert_eq!(pids, expected);
+
+        // Negated keyword selects the other processes
+        for negated in [format!("-{pid_key}"), format!("!{pid_key}")] {
+            let pids = filtered_pids(&["--", &negated], config).unwrap();
+            assert!(!pids.contains(&pid));
+            assert!(pids.contains(&ppid));
+        }
+        let pids = filtered_pids(&["--and", "--", &format!("!{pid_key}"), &ppid_key], config);
+        assert_eq!(pids.unwrap(), vec![ppid]);
+    }
+
+    #[test]
+    #[cfg(feature = "docker")]
+    fn test_filter_qualified_unavailable() {
+        let config = r#"
+[docker]
+path = "/nonexistent/docker.sock"
+[[columns]]
+kind = "Pid"
+"#;
+        let pid = std::process::id() as i32;
+
+        // Unavailable column matches no process, and the negated keyword matches all
+        assert!(filtered_pids(&["docker:web"], config).unwrap().is_empty());
+        assert!(
+            filtered_pids(&["--regex", "docker:^web"], config)
+                .unwrap()
+                .is_empty()
+        );
+        let pids = filtered_pids(&["!docker:web"], config).unwrap();
+        assert!(pids.contains(&pid));
+        let pids = filtered_pids(&["--or", "docker:web", &format!("pid:{pid}")], config);
+        assert_eq!(pids.unwrap(), vec![pid]);
+    }
+
+    #[test]
+    fn test_filter_colon_keyword() {
+        let config = r#"
+[[columns]]
+kind = "Pid"
+[[columns]]
+kind = "Command"
+nonnumeric_search = true
+"#;
+        // `rss` is a part of `VmRss`, but `rss:marker` is an ordinary keyword
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "sleep 10; true rss:marker"])
+            .spawn()
+            .unwrap();
+        // Wait for exec of the child
+        let cmdline = format!("/proc/{}/cmdline", child.id());
+        while !std::fs::read_to_string(&cmdline).is_ok_and(|x| x.contains("rss:marker")) {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let pids = filtered_pids(&["rss:marker"], config);
+        let _ = child.kill();
+        let _ = child.wait();
+        assert_eq!(pids.unwrap(), vec![child.id() as i32]);
+    }
+
+    #[test]
+    fn test_delimited_lines() {
+        let config = r#"
+[[columns]]
+kind = "Pid"
+numeric_search = true
+[[columns]]
+kind = "Separator"
+[[columns]]
+kind = "FileName"
+header = "Name, \"comm\""
+[[columns]]
+kind = "VmRss"
+"#;
+        let (view, pid) = view_self(&["--format", "csv"], config);
+        let lines = view.delimited_lines(&ArgFormat::Csv, false);
+        assert_eq!(lines.len(), 2);
+        // Separator is not a data column, and the header is quoted by RFC 4180
+        assert_eq!(lines[0], "PID,\"Name, \"\"comm\"\"\",VmRSS");
+
+        let fields: Vec<_> = lines[1].split(',').collect();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0], pid.to_string());
+        let comm = std::fs::read_to_string("/proc/self/comm").unwrap();
+        assert_eq!(fields[1], comm.trim());
+        // Numeric columns are raw values instead of `1.2M`
+        assert!(fields[2].parse::<u64>().unwrap() > 0);
+
+        let lines = view.delimited_lines(&ArgFormat::Tsv, true);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            lines[0].replace('\t', ","),
+            view.delimited_lines(&ArgFormat::Csv, true)[0]
+        );
+    }
+
+    #[test]
+    fn test_json_object() {
+        let config = r#"
+[[columns]]
+kind = "Pid"
+numeric_search = true
+header = "Process"
+[[columns]]
+kind = "Tree"
+[[columns]]
+kind = "FileName"
+[[columns]]
+kind = "TcpPort"
+[[columns]]
+kind = "VmRss"
+"#;
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let (view, pid) = view_self(&["--json"], config);
+        let object = view.json_object(pid);
+        // Keys are kind names in KIND_LIST instead of headers, and Tree is omitted
+        let keys: Vec<_> = object.keys().collect();
+        assert_eq!(keys, vec!["Pid", "FileName", "TcpPort", "VmRss"]);
+
+        let comm = std::fs::read_to_string("/proc/self/comm").unwrap();
+        assert_eq!(object["Pid"], serde_json::Value::from(pid));
+        assert_eq!(object["FileName"], serde_json::Value::from(comm.trim()));
+        assert!(object["TcpPort"].as_array().unwrap().contains(&port.into()));
+        assert!(object["VmRss"].as_u64().unwrap() > 0);
+
+        // Unavailable values are null
+        let object = view.json_object(-1);
+        assert!(object.values().all(|x| x.is_null()));
+    }
+
+    #[test]
+    fn test_json_object_all() {
+        let (view, pid) = view_self(&["--json"], CONFIG_ALL);
+        let object = view.json_object(pid);
+        let kinds: HashSet<_> = view.data_columns().map(|c| &c.kind).collect();
+        assert_eq!(object.len(), kinds.len());
+
+        // Every column is serialized to valid JSON
+        let text = serde_json::to_string(&object).unwrap();
+        let parsed: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed, object);
+    }
+
+    #[test]
+    fn test_template() {
+        let config = r#"
+[[columns]]
+kind = "Pid"
+numeric_search = true
+[templates]
+ps = "{pid:>8}|{filename}|{vmrss!raw}|{vmrss}"
+"#;
+        let (view, pid) = view_self(&["--format-template", "ps"], config);
+        let line = view.template.as_ref().unwrap().render(pid, &view.columns);
+        let fields: Vec<_> = line.split('|').collect();
+        assert_eq!(fields.len(), 4);
+
+        let comm = std::fs::read_to_string("/proc/self/comm").unwrap();
+        assert_eq!(fields[0], format!("{pid:>8}"));
+        // Columns referred only by the template are added as hidden columns
+        assert_eq!(fields[1], comm.trim());
+        let vmrss = fields[2].parse::<u64>().unwrap();
+        assert_eq!(fields[3], crate::util::bytify(vmrss));
+    }
+
+    #[test]
+    fn test_apply_diff_without_baseline() {
+        let pid = std::process::id() as i32;
+        let pid_str = pid.to_string();
+        let mut config: Config = toml::from_str(CONFIG_DEFAULT).unwrap();
+        config.pager.mode = ConfigPagerMode::Disable;
+        config.display.show_self = true;
+
+        let mut opt = Opt::parse_from(["procs", "--diff", &pid_str]);
+        let mut view = View::new(&mut opt, &config, false).unwrap();
+        view.apply_diff(None, &opt, &config);
+        view.filter(&opt, &config, 1).unwrap();
+
+        // The first update of watch mode shows absolute values instead of nothing
+        assert_eq!(view.visible_pids, vec![pid]);
+        assert!(view.diff.is_none());
+        assert!(!view.json_object(pid).contains_key("diff"));
+    }
+
+    #[test]
+    fn test_replay_system_state() {
+        let pid = std::process::id() as i32;
+        let path = std::env::temp_dir().join(format!("procs_view_replay_{pid}"));
+        let procs: Vec<_> = collect_proc(
+            Duration::from_millis(0),
+            false,
+            false,
+            &None,
+            1,
+            DataSource::RECORD,
+            false,
+        )
+        .into_iter()
+        .filter(|x| x.pid == pid)
+        .collect();
+        let stat = procs[0].curr_proc.stat().clone();
+
+        // Recorded on a host booted at 2001/09/09, 2 hours after this process started
+        let page_size = crate::util::procfs_system_info(&None).page_size;
+        let boot_time = 1_000_000_000;
+        let start_time = boot_time * 1000 + stat.starttime as i64 * 10;
+        let state = crate::util::SystemState {
+            time: start_time + 2 * 3600 * 1000,
+            boot_time,
+            ticks_per_second: 100,
+            page_size,
+            mem_total: stat.rss * page_size * 4,
+        };
+        crate::record::save(&path, &procs, state, &None).unwrap();
+
+        let config = r#"
+[[columns]]
+kind = "Pid"
+numeric_search = true
+[[columns]]
+kind = "StartTime"
+[[columns]]
+kind = "ElapsedTime"
+[[columns]]
+kind = "UsageMem"
+"#;
+        let (view, pid) = view_self(&["--replay", path.to_str().unwrap()], config);
+        let content = |i: usize| view.columns[i].column.display_raw(pid).unwrap();
+        let start_time = Local.timestamp_millis_opt(start_time).unwrap();
+        assert_eq!(content(1), start_time.format("%Y/%m/%d %H:%M").to_string());
+        assert_eq!(content(2), "2.0hours");
+        assert_eq!(content(3), "25.0");
+
+        // The recorded state is not used by other views
+        let (view, pid) = view_self(&[], config);
+        assert_ne!(view.columns[2].column.display_raw(pid).unwrap(), "2.0hours");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_replay_sockets() {
+        let path = std::env::temp_dir().join(format!("procs_view_sockets_{}", std::process::id()));
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        let config = r#"
+[[columns]]
+kind = "Pid"
+numeric_search = true
+[[columns]]
+kind = "TcpPort"
+"#;
+        view_self(&["--record", path.to_str().unwrap()], config);
+        drop(listener);
+
+        // The recorded socket table is used instead of the current one
+        let (view, pid) = view_self(&["--replay", path.to_str().unwrap()], config);
+        let ports = view.columns[1].column.display_raw(pid).unwrap();
+        assert!(ports.contains(&port), "{ports}");
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
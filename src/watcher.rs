@@ -2,7 +2,7 @@ use crate::Opt;
 use crate::config::*;
 use crate::search_regex::SearchRegex;
 use crate::term_info::TermInfo;
-use crate::util::{get_theme, has_regex_syntax};
+use crate::util::{get_theme, has_regex_syntax, parse_qualified_keyword};
 use crate::view::View;
 use anyhow::Error;
 use chrono::offset::Local;
@@ -60,6 +60,56 @@ impl Watcher {
         });
     }
 
+    fn start_json_lines(opt: &mut Opt, config: &Config, interval: u64) -> Result<(), Error> {
+        let theme = get_theme(opt, config);
+
+        let (tx_cmd, rx_cmd) = channel();
+
+        // Keyboard is not available if output is redirected to pipeline
+        if console::user_attended() {
+            Watcher::spawn_cmd(tx_cmd.clone());
+        }
+
+        let (tx_sleep, rx_sleep) = channel();
+        Watcher::spawn_sleep(rx_sleep, tx_cmd, interval);
+
+        let mut baseline = None;
+        'outer: loop {
+            let mut view = View::new(opt, config, false)?;
+            opt.replay_index += 1;
+            if opt.diff {
+                view.apply_diff(baseline.take(), opt, config);
+                baseline = Some(view.take_proc());
+            }
+            view.filter(opt, config, 0)?;
+
+            if let Err(e) = view.display(opt, config, &theme) {
+                // Stop quietly when the reader of pipeline is closed
+                let broken_pipe = e
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|e| e.kind() == std::io::ErrorKind::BrokenPipe);
+                if broken_pipe {
+                    tx_sleep.send(Command::Quit)?;
+                    break 'outer;
+                }
+                return Err(e);
+            }
+
+            tx_sleep.send(Command::Sleep)?;
+            loop {
+                match rx_cmd.recv()? {
+                    Command::Wake => break,
+                    Command::Quit | Command::Key(b'q') => {
+                        tx_sleep.send(Command::Quit)?;
+                        break 'outer;
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn display_header(
         term_info: &TermInfo,
         opt: &Opt,
@@ -67,6 +117,7 @@ impl Watcher {
         regex_editing: bool,
         regex_buffer: &str,
         regex_error: &Option<String>,
+        collecting_baseline: bool,
     ) -> Result<usize, Error> {
         let header = if opt.tree {
             format!(
@@ -103,6 +154,11 @@ impl Watcher {
             }
         }
 
+        if collecting_baseline {
+            term_info
+                .write_line(" Diff: collecting baseline, changes are shown from the next update")?;
+        }
+
         term_info.write_line("")?;
         let mut lines = result.div_ceil(term_info.width) + 1;
         if opt.regex || opt.smart {
@@ -111,10 +167,17 @@ impl Watcher {
                 lines += 1;
             }
         }
+        if collecting_baseline {
+            lines += 1;
+        }
         Ok(lines)
     }
 
     pub fn start(opt: &mut Opt, config: &Config, interval: u64) -> Result<(), Error> {
+        if opt.json_lines {
+            return Watcher::start_json_lines(opt, config, interval);
+        }
+
         let theme = get_theme(opt, config);
 
         let (tx_cmd, rx_cmd) = channel();
@@ -134,8 +197,15 @@ impl Watcher {
         let mut regex_editing = false;
         let mut regex_buffer = String::new();
         let mut regex_error: Option<String> = None;
+        let mut baseline = None;
         'outer: loop {
             let mut view = View::new(opt, config, true)?;
+            opt.replay_index += 1;
+            let collecting_baseline = opt.diff && baseline.is_none();
+            if opt.diff {
+                view.apply_diff(baseline.take(), opt, config);
+                baseline = Some(view.take_proc());
+            }
 
             // Override sort_info by key
             if !opt.tree {
@@ -155,6 +225,7 @@ impl Watcher {
                 regex_editing,
                 &regex_buffer,
                 &regex_error,
+                collecting_baseline,
             )?;
 
             view.filter(opt, config, header_lines)?;
@@ -211,17 +282,21 @@ impl Watcher {
                                         regex_error = None;
                                         regex_editing = false;
                                     } else {
-                                        let use_regex = opt.regex
-                                            || (opt.smart && has_regex_syntax(&candidate));
+                                        // Qualified keyword applies regex to the value part only
+                                        let pattern = parse_qualified_keyword(&candidate)
+                                            .map(|x| x.keyword)
+                                            .unwrap_or_else(|| candidate.clone());
+                                        let use_regex =
+                                            opt.regex || (opt.smart && has_regex_syntax(&pattern));
                                         if use_regex {
                                             let ignore_case = match config.search.case {
                                                 ConfigSearchCase::Smart => {
-                                                    candidate == candidate.to_ascii_lowercase()
+                                                    pattern == pattern.to_ascii_lowercase()
                                                 }
                                                 ConfigSearchCase::Insensitive => true,
                                                 ConfigSearchCase::Sensitive => false,
                                             };
-                                            match SearchRegex::new(&candidate, ignore_case) {
+                                            match SearchRegex::new(&pattern, ignore_case) {
                                                 Ok(_) => {
                                                     opt.keyword = vec![candidate];
                                                     regex_error = None;